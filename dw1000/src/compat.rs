@@ -0,0 +1,160 @@
+//! Compatibility with `embedded-hal` 1.0
+//!
+//! This driver is built on the `embedded-hal` 0.2 traits. Many newer HALs only
+//! implement `embedded-hal` 1.0, though. The wrappers in this module can be
+//! used to bridge that gap.
+//!
+//! Wrap the SPI bus and chip select pin in [`Compat`] before passing them to
+//! [`DW1000::new`]:
+//!
+//! ``` rust,ignore
+//! use dw1000::{compat::Compat, DW1000};
+//!
+//! let dw1000 = DW1000::new(Compat(spi_bus), Compat(cs_pin));
+//! ```
+//!
+//! If you have an `SpiDevice` that manages chip select itself, use
+//! [`DW1000::new_from_spi_device`] instead.
+//!
+//! This module is only available, if the `eh1` feature is enabled.
+//!
+//! [`DW1000::new`]: ../hl/struct.DW1000.html#method.new
+//! [`DW1000::new_from_spi_device`]: ../hl/struct.DW1000.html#method.new_from_spi_device
+
+
+use embedded_hal::{
+    blocking::spi,
+    digital::v2::OutputPin,
+};
+use embedded_hal_1 as eh1;
+
+
+/// Wraps an `embedded-hal` 1.0 type, implementing the 0.2 traits for it
+///
+/// Implements the blocking SPI traits for types that implement
+/// `embedded_hal::spi::SpiBus` 1.0, and `OutputPin` for types that implement
+/// `embedded_hal::digital::OutputPin` 1.0.
+#[derive(Debug)]
+pub struct Compat<T>(pub T);
+
+impl<T> Compat<T> {
+    /// Returns the wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> spi::Transfer<u8> for Compat<T>
+    where
+        T: eh1::spi::SpiBus<u8>,
+{
+    type Error = T::Error;
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8])
+        -> Result<&'w [u8], Self::Error>
+    {
+        self.0.transfer_in_place(words)?;
+
+        // `SpiBus` methods may return before the transfer has finished. The
+        // driver changes the chip select signal right after this returns, so
+        // we need to wait.
+        self.0.flush()?;
+
+        Ok(words)
+    }
+}
+
+impl<T> spi::Write<u8> for Compat<T>
+    where
+        T: eh1::spi::SpiBus<u8>,
+{
+    type Error = T::Error;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.0.write(words)?;
+        self.0.flush()?;
+
+        Ok(())
+    }
+}
+
+impl<T> OutputPin for Compat<T>
+    where
+        T: eh1::digital::OutputPin,
+{
+    type Error = T::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low()
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high()
+    }
+}
+
+
+/// Wraps an `embedded-hal` 1.0 `SpiDevice`, implementing the 0.2 SPI traits
+///
+/// An `SpiDevice` manages chip select itself, so it can share the bus with
+/// other devices (for example using `embedded-hal-bus`). Every register
+/// access of the driver is issued as a single transaction on the device.
+///
+/// Use [`DW1000::new_from_spi_device`] to create a driver instance from an
+/// `SpiDevice`.
+///
+/// [`DW1000::new_from_spi_device`]: ../hl/struct.DW1000.html#method.new_from_spi_device
+#[derive(Debug)]
+pub struct Device<T>(pub T);
+
+impl<T> Device<T> {
+    /// Returns the wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> spi::Transfer<u8> for Device<T>
+    where
+        T: eh1::spi::SpiDevice<u8>,
+{
+    type Error = T::Error;
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8])
+        -> Result<&'w [u8], Self::Error>
+    {
+        self.0.transfer_in_place(words)?;
+        Ok(words)
+    }
+}
+
+impl<T> spi::Write<u8> for Device<T>
+    where
+        T: eh1::spi::SpiDevice<u8>,
+{
+    type Error = T::Error;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.0.write(words)
+    }
+}
+
+
+/// Placeholder for the chip select pin, if chip select is managed elsewhere
+///
+/// Used together with [`Device`], whose `SpiDevice` already takes care of chip
+/// select.
+#[derive(Debug)]
+pub struct NoChipSelect;
+
+impl OutputPin for NoChipSelect {
+    type Error = core::convert::Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
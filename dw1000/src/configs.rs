@@ -3,7 +3,7 @@
 //! This module houses the datastructures that control how frames are transmitted and received.
 //! The configs are passed to the send and receive functions.
 
-use crate::Error;
+use crate::{mac, time::Duration, Error};
 use embedded_hal::{blocking::spi, digital::v2::OutputPin};
 
 /// Transmit configuration
@@ -21,6 +21,32 @@ pub struct TxConfig {
     pub channel: UwbChannel,
     /// The SFD sequence that is used to transmit a frame.
     pub sfd_sequence: SfdSequence,
+    /// Enables or disables smart TX power control
+    ///
+    /// Smart TX power control boosts the power of frames that are shorter
+    /// than 1 ms, and must not be used for longer frames. `None` enables it
+    /// automatically, depending on the duration of the frame.
+    ///
+    /// Defaults to `None`.
+    pub smart_tx_power: Option<bool>,
+    /// The IEEE 802.15.4 frame version of frames sent by [`DW1000::send`]
+    ///
+    /// Frames passed to [`DW1000::send_frame`] keep their own version.
+    ///
+    /// Defaults to `mac::FrameVersion::Ieee802154_2006`.
+    ///
+    /// [`DW1000::send`]: ../hl/struct.DW1000.html#method.send
+    /// [`DW1000::send_frame`]: ../hl/struct.DW1000.html#method.send_frame
+    pub frame_version: mac::FrameVersion,
+    /// Suppresses the automatic FCS
+    ///
+    /// If true, the DW1000 doesn't append the 2-byte FCS. Instead, the last
+    /// two bytes of the frame are sent in its place, so the FCS needs to be
+    /// included at the end of the payload. This is set or cancelled for every
+    /// transmission, so it doesn't carry over to the next frame.
+    ///
+    /// Defaults to `false`.
+    pub suppress_fcs: bool,
 }
 
 impl Default for TxConfig {
@@ -32,6 +58,86 @@ impl Default for TxConfig {
             preamble_length: Default::default(),
             channel: Default::default(),
             sfd_sequence: Default::default(),
+            smart_tx_power: None,
+            frame_version: mac::FrameVersion::Ieee802154_2006,
+            suppress_fcs: false,
+        }
+    }
+}
+
+impl TxConfig {
+    /// Computes the on-air duration of a frame, in picoseconds
+    ///
+    /// `frame_len` is the length of the frame, including the FCS.
+    pub(crate) fn frame_duration_ps(&self, frame_len: usize) -> u64 {
+        // Durations of preamble symbols, PHR bits and data bits, in
+        // picoseconds. Values taken from Tables 1 and 6 of the DW1000 User
+        // Manual.
+        let symbol_ps = self.pulse_repetition_frequency
+            .get_preamble_symbol_duration() * 1000 / 64;
+        let (phr_bit_ps, data_bit_ps) = match self.bitrate {
+            BitRate::Kbps110  => (8_205_130, 8_205_130),
+            BitRate::Kbps850  => (1_025_640, 1_025_640),
+            BitRate::Kbps6800 => (1_025_640,   128_210),
+        };
+
+        let sfd_symbols = match (self.bitrate, self.sfd_sequence) {
+            (BitRate::Kbps110, _) => 64,
+            (_, SfdSequence::DecawaveAlt) => 16,
+            (_, _) => 8,
+        };
+        let shr_symbols = self.preamble_length.get_symbol_count() as u64
+            + sfd_symbols;
+
+        // The PHR consists of 21 bits. The data is protected by Reed-Solomon
+        // coding, which adds 48 parity bits for every block of up to 330 bits.
+        const PHR_BITS: u64 = 21;
+        let data_bits = frame_len as u64 * 8;
+        let data_bits = data_bits + data_bits.div_ceil(330) * 48;
+
+        shr_symbols * symbol_ps
+            + PHR_BITS * phr_bit_ps
+            + data_bits * data_bit_ps
+    }
+
+    /// Computes how long a frame takes on air
+    ///
+    /// `payload_len` is the length of the PHY payload, which is the complete
+    /// MAC frame, including header and the 2-byte FCS. The result covers the
+    /// preamble, SFD, PHR, and payload, based on the bitrate, preamble length,
+    /// PRF, and SFD sequence of this configuration. It is rounded up to the
+    /// next DW1000 time unit.
+    ///
+    /// This can be used to size TDMA slots, or to compute the lead time for
+    /// delayed transmissions.
+    pub fn air_time(&self, payload_len: usize) -> Duration {
+        // One DW1000 time unit is 1 / (128 * 499.2 MHz), so there are
+        // 63.8976 time units per nanosecond.
+        const UNITS_PER_10_US: u64 = 638_976;
+        const PS_PER_10_US:    u64 = 10_000_000;
+
+        let ps    = self.frame_duration_ps(payload_len);
+        let units = (ps * UNITS_PER_10_US).div_ceil(PS_PER_10_US);
+
+        // Even a 1023-byte frame at 110 kbps takes far less than the 17
+        // seconds that fit into a `Duration`, so this should never panic.
+        Duration::new(units).unwrap()
+    }
+
+    /// Transmit configuration for long range
+    ///
+    /// Uses the lowest bit rate (110 kbps), a long preamble (2048 symbols),
+    /// 64 MHz PRF, and the Decawave SFD sequence. This maximizes range, at the
+    /// cost of much longer frame durations (several milliseconds per frame),
+    /// and thus lower throughput and higher power consumption. Use together
+    /// with [`RxConfig::long_range`].
+    pub fn long_range() -> Self {
+        TxConfig {
+            bitrate: BitRate::Kbps110,
+            preamble_length: PreambleLength::Symbols2048,
+            pulse_repetition_frequency: PulseRepetitionFrequency::Mhz64,
+            sfd_sequence: SfdSequence::Decawave,
+            ..Default::default()
         }
     }
 }
@@ -44,7 +150,8 @@ pub struct RxConfig {
     /// Enable frame filtering
     ///
     /// If true, only frames directly addressed to this node and broadcasts will
-    /// be received.
+    /// be received. Broadcasts are frames sent to the short address `0xffff`,
+    /// with either the broadcast PAN ID `0xffff` or this node's PAN ID.
     ///
     /// Defaults to `true`.
     pub frame_filtering: bool,
@@ -60,6 +167,94 @@ pub struct RxConfig {
     pub channel: UwbChannel,
     /// The type of SFD sequence that will be scanned for.
     pub sfd_sequence: SfdSequence,
+    /// Preamble detection timeout
+    ///
+    /// If no preamble is detected within this time after the receiver is
+    /// enabled, the receiver gives up. The timeout is rounded down to a
+    /// multiple of the PAC size. `None` disables the timeout.
+    ///
+    /// Defaults to `None`.
+    pub preamble_timeout: Option<Duration>,
+    /// SFD detection timeout, in preamble symbols
+    ///
+    /// If no SFD is detected within this number of symbols after the preamble
+    /// has been detected, the receiver gives up, and reports
+    /// [`Error::SfdTimeout`]. Longer preambles need a longer SFD timeout.
+    /// `None` selects the recommended value for the expected preamble length
+    /// (see [`RxConfig::get_recommended_sfd_timeout`]).
+    ///
+    /// Defaults to `None`.
+    pub sfd_timeout: Option<u16>,
+    /// The preamble acquisition chunk (PAC) size
+    ///
+    /// `None` selects the recommended PAC size for the expected preamble
+    /// length. The PAC size must be smaller than the expected preamble length.
+    ///
+    /// Defaults to `None`.
+    pub pac_size: Option<PacSize>,
+    /// The minimum length of a received frame, including the 2-byte FCS
+    ///
+    /// Shorter frames are rejected with [`Error::FrameTooShort`], before they
+    /// are decoded. This can be used to filter out garbage that passed the FCS
+    /// check by chance.
+    ///
+    /// Defaults to `0`, meaning no frames are rejected.
+    pub min_frame_len: usize,
+    /// Keep the contents of frames that failed to be received
+    ///
+    /// If true, [`DW1000::wait`] and [`DW1000::wait_timeout`] copy the
+    /// corrupted frame into the provided buffer on an FCS or PHY header error,
+    /// and return [`Error::CorruptedFcs`] or [`Error::CorruptedPhy`] instead of
+    /// [`Error::Fcs`] or [`Error::Phy`]. This can be useful for debugging, for
+    /// example to find out which part of a frame was corrupted.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`DW1000::wait`]: ../hl/struct.DW1000.html#method.wait
+    /// [`DW1000::wait_timeout`]: ../hl/struct.DW1000.html#method.wait_timeout
+    pub keep_corrupted_frames: bool,
+}
+
+impl RxConfig {
+    /// Receive configuration for long range
+    ///
+    /// Matches [`TxConfig::long_range`]. The PAC size and SFD timeout are
+    /// derived from the expected preamble length.
+    pub fn long_range() -> Self {
+        RxConfig {
+            bitrate: BitRate::Kbps110,
+            expected_preamble_length: PreambleLength::Symbols2048,
+            pulse_repetition_frequency: PulseRepetitionFrequency::Mhz64,
+            sfd_sequence: SfdSequence::Decawave,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the PAC size in symbols
+    ///
+    /// Returns the configured PAC size, or the recommended one for the expected
+    /// preamble length, if none was configured.
+    pub fn get_pac_size(&self) -> u8 {
+        match self.pac_size {
+            Some(pac_size) => pac_size as u8,
+            None => self.expected_preamble_length.get_recommended_pac_size(),
+        }
+    }
+
+    /// Gets the recommended SFD timeout, based on the expected preamble length
+    pub fn get_recommended_sfd_timeout(&self) -> u16 {
+        // Formula taken from section 7.2.40.7 of the DW1000 User Manual:
+        // preamble length + 1 + SFD length - PAC size
+        let sfd_length = match (self.bitrate, self.sfd_sequence) {
+            (BitRate::Kbps110, _) => 64,
+            (_, SfdSequence::DecawaveAlt) => 16,
+            (_, _) => 8,
+        };
+        let pac_size = self.get_pac_size();
+
+        self.expected_preamble_length.get_symbol_count() + 1 + sfd_length
+            - pac_size as u16
+    }
 }
 
 impl Default for RxConfig {
@@ -71,6 +266,11 @@ impl Default for RxConfig {
             expected_preamble_length: Default::default(),
             channel: Default::default(),
             sfd_sequence: Default::default(),
+            preamble_timeout: None,
+            sfd_timeout: None,
+            pac_size: None,
+            min_frame_len: 0,
+            keep_corrupted_frames: false,
         }
     }
 }
@@ -80,6 +280,11 @@ impl Default for RxConfig {
 pub enum BitRate {
     /// 110 kilobits per second.
     /// This is an unofficial extension from decawave.
+    ///
+    /// Requires a preamble of at least 1536 symbols. When receiving, the
+    /// 110 kbps receiver mode (SYS_CFG.RXM110K), the matching DRX tuning
+    /// values, and the longer SFD timeout are configured automatically. See
+    /// [`RxConfig::long_range`].
     Kbps110 = 0b00,
     /// 850 kilobits per second.
     Kbps850 = 0b01,
@@ -94,6 +299,16 @@ impl Default for BitRate {
 }
 
 impl BitRate {
+    /// Decodes the bitrate from its register representation
+    pub(crate) fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0b00 => Some(BitRate::Kbps110),
+            0b01 => Some(BitRate::Kbps850),
+            0b10 => Some(BitRate::Kbps6800),
+            _    => None,
+        }
+    }
+
     /// Gets the recommended drx_tune0b value for the bitrate and sfd.
     pub fn get_recommended_drx_tune0b(&self, sfd_sequence: SfdSequence) -> u16 {
         // Values are taken from Table 30 of the DW1000 User Manual.
@@ -124,6 +339,15 @@ impl Default for PulseRepetitionFrequency {
 }
 
 impl PulseRepetitionFrequency {
+    /// Decodes the PRF from its register representation
+    pub(crate) fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0b01 => Some(PulseRepetitionFrequency::Mhz16),
+            0b10 => Some(PulseRepetitionFrequency::Mhz64),
+            _    => None,
+        }
+    }
+
     /// Gets the recommended value for the drx_tune1a register based on the PRF
     pub fn get_recommended_drx_tune1a(&self) -> u16 {
         // Values taken from Table 31 of the DW1000 User Manual.
@@ -133,6 +357,34 @@ impl PulseRepetitionFrequency {
         }
     }
 
+    /// Gets the recommended value for the agc_tune1 register based on the PRF
+    pub fn get_recommended_agc_tune1(&self) -> u16 {
+        // Values taken from Table 24 of the DW1000 User Manual.
+        match self {
+            PulseRepetitionFrequency::Mhz16 => 0x8870,
+            PulseRepetitionFrequency::Mhz64 => 0x889B,
+        }
+    }
+
+    /// Gets the recommended value for the lde_cfg2 register based on the PRF
+    pub fn get_recommended_lde_cfg2(&self) -> u16 {
+        // Values taken from Table 50 of the DW1000 User Manual.
+        match self {
+            PulseRepetitionFrequency::Mhz16 => 0x1607,
+            PulseRepetitionFrequency::Mhz64 => 0x0607,
+        }
+    }
+
+    /// Gets the duration of a preamble symbol, in units of 1/64 ns
+    pub fn get_preamble_symbol_duration(&self) -> u64 {
+        // Values taken from Table 6 of the DW1000 User Manual (993.59 ns and
+        // 1017.63 ns, respectively).
+        match self {
+            PulseRepetitionFrequency::Mhz16 => 63_590,
+            PulseRepetitionFrequency::Mhz64 => 65_128,
+        }
+    }
+
     /// Gets the recommended value for the drx_tune2 register based on the PRF and PAC size
     pub fn get_recommended_drx_tune2<SPI, CS>(&self, pac_size: u8) -> Result<u32, Error<SPI, CS>>
     where
@@ -201,6 +453,37 @@ impl Default for PreambleLength {
 }
 
 impl PreambleLength {
+    /// Decodes the preamble length from its register representation
+    ///
+    /// Expects TXPSR in the upper two bits, PE in the lower two bits.
+    pub(crate) fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0b0100 => Some(PreambleLength::Symbols64),
+            0b0101 => Some(PreambleLength::Symbols128),
+            0b0110 => Some(PreambleLength::Symbols256),
+            0b0111 => Some(PreambleLength::Symbols512),
+            0b1000 => Some(PreambleLength::Symbols1024),
+            0b1001 => Some(PreambleLength::Symbols1536),
+            0b1010 => Some(PreambleLength::Symbols2048),
+            0b1100 => Some(PreambleLength::Symbols4096),
+            _      => None,
+        }
+    }
+
+    /// Gets the number of preamble symbols.
+    pub fn get_symbol_count(&self) -> u16 {
+        match self {
+            PreambleLength::Symbols64 => 64,
+            PreambleLength::Symbols128 => 128,
+            PreambleLength::Symbols256 => 256,
+            PreambleLength::Symbols512 => 512,
+            PreambleLength::Symbols1024 => 1024,
+            PreambleLength::Symbols1536 => 1536,
+            PreambleLength::Symbols2048 => 2048,
+            PreambleLength::Symbols4096 => 4096,
+        }
+    }
+
     /// Gets the recommended PAC size based on the preamble length.
     pub fn get_recommended_pac_size(&self) -> u8 {
         // Values are taken from Table 6 of the DW1000 User manual
@@ -253,6 +536,35 @@ impl PreambleLength {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// The preamble acquisition chunk (PAC) size, in symbols
+///
+/// Larger PAC sizes improve reception of long preambles. See table 6 of the
+/// user manual for recommended values.
+pub enum PacSize {
+    /// 8 symbols
+    Symbols8 = 8,
+    /// 16 symbols
+    Symbols16 = 16,
+    /// 32 symbols
+    Symbols32 = 32,
+    /// 64 symbols
+    Symbols64 = 64,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// The PHY header mode, which determines the maximum frame length
+///
+/// Both sender and receiver must use the same mode, or frames won't be
+/// decoded.
+pub enum PhrMode {
+    /// Standard IEEE 802.15.4 mode, with frames of up to 127 bytes
+    #[default]
+    Standard = 0b00,
+    /// Proprietary long frames mode, with frames of up to 1023 bytes
+    Extended = 0b11,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 /// An enum that allows the selection between different SFD sequences
 ///
@@ -399,3 +711,126 @@ impl UwbChannel {
         }
     }
 }
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// Initialization configuration
+///
+/// Used by [`DW1000::init_with_config`].
+///
+/// [`DW1000::init_with_config`]: ../hl/struct.DW1000.html#method.init_with_config
+pub struct InitConfig {
+    /// Load the LDE microcode from ROM
+    ///
+    /// The leading edge detection (LDE) algorithm determines the precise RX
+    /// time stamp. Loading its microcode takes some time, which can be saved,
+    /// if the application doesn't need precise RX time stamps (for example,
+    /// for pure data communication without ranging). If it isn't loaded, the
+    /// LDE is disabled, and RX time stamps are inaccurate.
+    ///
+    /// Defaults to `true`.
+    pub load_lde: bool,
+    /// The RX and TX antenna delays, in that order
+    ///
+    /// Applied during initialization, so ranging works from the first frame.
+    /// `None` leaves the antenna delays at their reset value of zero. They can
+    /// still be set later, using [`DW1000::set_antenna_delay`].
+    ///
+    /// Defaults to `None`.
+    ///
+    /// [`DW1000::set_antenna_delay`]: ../hl/struct.DW1000.html#method.set_antenna_delay
+    pub antenna_delay: Option<(u16, u16)>,
+    /// Load the factory antenna delay for this channel and PRF from OTP
+    ///
+    /// Decawave only calibrates the antenna delay for channels 2 and 5. If a
+    /// value was programmed into OTP for the given channel and PRF, it is used
+    /// for both the RX and TX antenna delay, taking precedence over
+    /// `antenna_delay`. Otherwise, `antenna_delay` is applied. See
+    /// [`DW1000::load_factory_calibration`].
+    ///
+    /// Defaults to `None`.
+    ///
+    /// [`DW1000::load_factory_calibration`]: ../hl/struct.DW1000.html#method.load_factory_calibration
+    pub factory_antenna_delay: Option<(UwbChannel, PulseRepetitionFrequency)>,
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        InitConfig {
+            load_lde: true,
+            antenna_delay: None,
+            factory_antenna_delay: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// Configures what the DW1000 does when waking up from sleep
+///
+/// Used by [`DW1000::enter_sleep`]. Each flag corresponds to a bit in the
+/// AON_WCFG register. See user manual, section 7.2.44.
+///
+/// To get a working receiver immediately after waking up, without having to
+/// initialize the DW1000 again, `restore_config`, `load_lde` and
+/// `load_ldotune` are required. This is what the default configuration does.
+/// `load_length64` is required in addition, if a preamble length of 64
+/// symbols is used.
+///
+/// [`DW1000::enter_sleep`]: ../hl/struct.DW1000.html#method.enter_sleep
+pub struct AonConfig {
+    /// Run the temperature and voltage ADC on wake-up (ONW_RADC)
+    ///
+    /// Defaults to `false`.
+    pub run_adc: bool,
+    /// Turn on the receiver on wake-up (ONW_RX)
+    ///
+    /// Defaults to `false`.
+    pub enable_receiver: bool,
+    /// Load the EUI from OTP on wake-up (ONW_LEUI)
+    ///
+    /// Defaults to `false`.
+    pub load_eui: bool,
+    /// Restore the user configuration from AON memory on wake-up (ONW_LDC)
+    ///
+    /// Without this, all registers are reset to their default values, and the
+    /// DW1000 needs to be initialized again.
+    ///
+    /// Defaults to `true`.
+    pub restore_config: bool,
+    /// Load the operating parameters for 64-symbol preambles on wake-up
+    /// (ONW_L64P)
+    ///
+    /// Defaults to `false`.
+    pub load_length64: bool,
+    /// Preserve the sleep configuration on wake-up (PRES_SLEEP)
+    ///
+    /// If set, the DW1000 can be put back to sleep without configuring sleep
+    /// again.
+    ///
+    /// Defaults to `false`.
+    pub preserve_sleep: bool,
+    /// Load the LDE microcode on wake-up (ONW_LLDE)
+    ///
+    /// Required to get RX timestamps after waking up.
+    ///
+    /// Defaults to `true`.
+    pub load_lde: bool,
+    /// Load the LDOTUNE value from OTP on wake-up (ONW_LLDO)
+    ///
+    /// Defaults to `true`.
+    pub load_ldotune: bool,
+}
+
+impl Default for AonConfig {
+    fn default() -> Self {
+        AonConfig {
+            run_adc:         false,
+            enable_receiver: false,
+            load_eui:        false,
+            restore_config:  true,
+            load_length64:   false,
+            preserve_sleep:  false,
+            load_lde:        true,
+            load_ldotune:    true,
+        }
+    }
+}
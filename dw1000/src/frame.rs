@@ -0,0 +1,475 @@
+//! Helpers for working with IEEE 802.15.4 MAC frames
+//!
+//! The MAC frame types themselves are defined in the [`mac`] module. This
+//! module provides additional helpers on top of those.
+//!
+//! [`mac`]: ../mac/index.html
+
+
+use byte::{
+    BytesExt as _,
+    ctx::Bytes,
+};
+
+use crate::mac;
+
+
+/// Builds an outgoing IEEE 802.15.4 MAC frame
+///
+/// The defaults match the frames created by [`DW1000::send`]: A data frame
+/// using the 2006 frame version, with no security, and all flags cleared. Use
+/// the methods to override whatever you need, then pass the result of
+/// [`FrameBuilder::build`] to [`DW1000::send_frame`].
+///
+/// [`DW1000::send`]: ../hl/struct.DW1000.html#method.send
+/// [`DW1000::send_frame`]: ../hl/struct.DW1000.html#method.send_frame
+#[derive(Debug)]
+pub struct FrameBuilder<'p> {
+    header:  mac::Header,
+    content: mac::FrameContent,
+    payload: &'p [u8],
+}
+
+impl<'p> FrameBuilder<'p> {
+    /// Creates a new builder for a frame containing `payload`
+    pub fn new(payload: &'p [u8]) -> Self {
+        FrameBuilder {
+            header: mac::Header {
+                frame_type:      mac::FrameType::Data,
+                version:         mac::FrameVersion::Ieee802154_2006,
+                security:        mac::Security::None,
+                frame_pending:   false,
+                ack_request:     false,
+                pan_id_compress: false,
+                destination:     None,
+                source:          None,
+                seq:             0,
+            },
+            content: mac::FrameContent::Data,
+            payload,
+        }
+    }
+
+    /// Sets the frame type
+    ///
+    /// For data and acknowledgement frames, this also sets the matching frame
+    /// content. For beacon and MAC command frames, use
+    /// [`FrameBuilder::content`] to set the content.
+    pub fn frame_type(mut self, frame_type: mac::FrameType) -> Self {
+        match frame_type {
+            mac::FrameType::Data =>
+                self.content = mac::FrameContent::Data,
+            mac::FrameType::Acknowledgement =>
+                self.content = mac::FrameContent::Acknowledgement,
+            _ =>
+                (),
+        }
+
+        self.header.frame_type = frame_type;
+        self
+    }
+
+    /// Sets the frame content
+    pub fn content(mut self, content: mac::FrameContent) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// Sets the frame version
+    pub fn version(mut self, version: mac::FrameVersion) -> Self {
+        self.header.version = version;
+        self
+    }
+
+    /// Sets the security field
+    pub fn security(mut self, security: mac::Security) -> Self {
+        self.header.security = security;
+        self
+    }
+
+    /// Sets the frame pending flag
+    pub fn frame_pending(mut self, frame_pending: bool) -> Self {
+        self.header.frame_pending = frame_pending;
+        self
+    }
+
+    /// Sets the acknowledgement request flag
+    pub fn ack_request(mut self, ack_request: bool) -> Self {
+        self.header.ack_request = ack_request;
+        self
+    }
+
+    /// Sets the PAN ID compression flag
+    pub fn pan_id_compress(mut self, pan_id_compress: bool) -> Self {
+        self.header.pan_id_compress = pan_id_compress;
+        self
+    }
+
+    /// Sets the destination address
+    ///
+    /// The addressing mode (short or extended) is determined by the address.
+    /// Frames sent to the broadcast address (PAN ID `0xffff`, short address
+    /// `0xffff`) pass the DW1000's frame filter on all nodes.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use byte::BytesExt as _;
+    /// use dw1000::{
+    ///     frame,
+    ///     mac,
+    ///     FrameBuilder,
+    /// };
+    ///
+    /// let broadcast = frame::broadcast();
+    /// let source = mac::Address::Short(
+    ///     mac::PanId(0x0d57),
+    ///     mac::ShortAddress(0x0001),
+    /// );
+    ///
+    /// let frame = FrameBuilder::new(b"discovery")
+    ///     .destination(Some(broadcast))
+    ///     .source(Some(source))
+    ///     .build();
+    ///
+    /// let mut buffer = [0; 127];
+    /// let mut len    = 0;
+    /// buffer.write_with(&mut len, frame, mac::FooterMode::None).unwrap();
+    ///
+    /// // Frame control and sequence number are followed by the destination PAN
+    /// // ID and address.
+    /// assert_eq!(&buffer[3..7], &[0xff, 0xff, 0xff, 0xff]);
+    /// ```
+    pub fn destination(mut self, destination: Option<mac::Address>) -> Self {
+        self.header.destination = destination;
+        self
+    }
+
+    /// Sets the source address
+    ///
+    /// The addressing mode (short or extended) is determined by the address.
+    pub fn source(mut self, source: Option<mac::Address>) -> Self {
+        self.header.source = source;
+        self
+    }
+
+    /// Sets the sequence number
+    pub fn seq(mut self, seq: u8) -> Self {
+        self.header.seq = seq;
+        self
+    }
+
+    /// Builds the frame
+    pub fn build(self) -> mac::Frame<'p> {
+        mac::Frame {
+            header:  self.header,
+            content: self.content,
+            payload: self.payload,
+            footer:  [0; 2],
+        }
+    }
+}
+
+
+/// Returns the broadcast address
+///
+/// This is the short address `0xffff` with the broadcast PAN ID `0xffff`.
+/// Frames sent to it pass the DW1000's frame filter on all nodes, and can be
+/// passed to [`DW1000::send`] like any other destination.
+///
+/// [`DW1000::send`]: ../hl/struct.DW1000.html#method.send
+pub fn broadcast() -> mac::Address {
+    mac::Address::Short(mac::PanId(0xffff), mac::ShortAddress(0xffff))
+}
+
+/// Indicates whether `address` is a broadcast address
+///
+/// Any short address `0xffff` is considered a broadcast address, regardless of
+/// its PAN ID. Extended addresses are never broadcast addresses.
+///
+/// # Example
+///
+/// ``` rust
+/// use dw1000::{
+///     frame,
+///     mac,
+/// };
+///
+/// assert!(frame::is_broadcast(&frame::broadcast()));
+/// assert!(frame::is_broadcast(&mac::Address::Short(
+///     mac::PanId(0x0d57),
+///     mac::ShortAddress(0xffff),
+/// )));
+/// assert!(!frame::is_broadcast(&mac::Address::Short(
+///     mac::PanId(0xffff),
+///     mac::ShortAddress(0x0001),
+/// )));
+/// ```
+pub fn is_broadcast(address: &mac::Address) -> bool {
+    match address {
+        mac::Address::Short(_, address) => address.0 == 0xffff,
+        mac::Address::Extended(_, _)    => false,
+    }
+}
+
+/// Generates a random short address
+///
+/// `entropy` is called to get random bytes, so this doesn't depend on any
+/// specific random number generator. The reserved addresses `0xfffe` (no
+/// short address assigned) and `0xffff` (broadcast) are never returned. If
+/// `entropy` returns one of them, new bytes are requested.
+///
+/// Avoiding collisions with other nodes is left to the join protocol, for
+/// example by picking a new address, if the current one is already in use.
+///
+/// # Example
+///
+/// ``` rust
+/// use dw1000::{
+///     frame,
+///     mac,
+/// };
+///
+/// // Not very random, but enough to show that reserved addresses are skipped.
+/// let mut bytes   = [0xff, 0xff, 0xfe, 0xff, 0x34, 0x12].iter().copied();
+/// let mut entropy = || bytes.next().unwrap();
+///
+/// let address = frame::random_short_address(&mut entropy);
+/// assert_eq!(address, mac::ShortAddress(0x1234));
+/// ```
+pub fn random_short_address(entropy: &mut impl FnMut() -> u8)
+    -> mac::ShortAddress
+{
+    loop {
+        let address = u16::from_le_bytes([entropy(), entropy()]);
+
+        if address < 0xfffe {
+            return mac::ShortAddress(address);
+        }
+    }
+}
+
+
+/// The maximum length of a MAC header, without the auxiliary security header
+///
+/// Frame control, sequence number, and two extended addresses with PAN IDs.
+const MAX_HEADER_LEN: usize = 2 + 1 + 2 * (2 + 8);
+
+/// Decodes a received frame, including secured frames
+///
+/// The [`mac`] module rejects frames that have the security enabled bit set.
+/// This function decodes those frames too, by parsing the
+/// [`AuxSecurityHeader`] that follows the addressing fields itself. The
+/// returned frame's payload starts after that header. As `mac::Security` has
+/// no way to express that a frame is secured, the returned header is the only
+/// indication of that. The payload is returned as it was received. Decrypting
+/// it and checking the MIC is left to the application.
+///
+/// The frame is decoded using `mac::FooterMode::None`.
+///
+/// [`mac`]: ../mac/index.html
+pub fn decode(bytes: &[u8])
+    -> Result<(mac::Frame<'_>, Option<AuxSecurityHeader>), byte::Error>
+{
+    // Security enabled bit of the frame control field. See IEEE 802.15.4-2011,
+    // section 5.2.1.1.2.
+    const SECURITY_ENABLED: u8 = 0b0000_1000;
+
+    if bytes.is_empty() || bytes[0] & SECURITY_ENABLED == 0 {
+        let frame = bytes.read_with(&mut 0, mac::FooterMode::None)?;
+        return Ok((frame, None));
+    }
+
+    // The header can't be decoded with the security enabled bit set, so
+    // decode a copy without it.
+    let mut header_bytes = [0; MAX_HEADER_LEN];
+    let header_len       = bytes.len().min(MAX_HEADER_LEN);
+    header_bytes[..header_len].copy_from_slice(&bytes[..header_len]);
+    header_bytes[0] &= !SECURITY_ENABLED;
+
+    let offset = &mut 0;
+    let header: mac::Header = header_bytes[..header_len].read(offset)?;
+
+    let (security, len) = AuxSecurityHeader::decode(&bytes[*offset..])?;
+    *offset += len;
+
+    let content = bytes.read_with(offset, &header)?;
+    let payload = bytes.read_with(offset, Bytes::Len(bytes.len() - *offset))?;
+
+    let frame = mac::Frame {
+        header,
+        content,
+        payload,
+        footer: [0; 2],
+    };
+
+    Ok((frame, Some(security)))
+}
+
+
+/// The auxiliary security header of a secured IEEE 802.15.4 frame
+///
+/// The DW1000 doesn't do any of the cryptography for secured frames, and the
+/// [`mac`] module doesn't handle the auxiliary security header itself. This
+/// type can be used to parse and create the header, so secured frames can
+/// coexist with other 802.15.4 stacks. Encrypting the payload and computing
+/// the MIC is left to the application.
+///
+/// The header directly follows the addressing fields. Received frames are
+/// decoded with [`decode`], which strips the header from the payload and
+/// makes it available as [`Message::security`]. `mac::Security` can't
+/// express that a frame is secured, so to send a secured frame, encode the
+/// header at the start of the payload, encode the frame into the transmit
+/// buffer, set the security enabled bit (bit 3 of the frame control field),
+/// and send it using [`DW1000::send_raw`].
+///
+/// See IEEE 802.15.4-2011, section 7.4.
+///
+/// [`mac`]: ../mac/index.html
+/// [`Message::security`]: ../hl/struct.Message.html#structfield.security
+/// [`DW1000::send_raw`]: ../hl/struct.DW1000.html#method.send_raw
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuxSecurityHeader {
+    /// The security level (0-7)
+    pub security_level: u8,
+
+    /// The frame counter
+    pub frame_counter: u32,
+
+    /// The key identifier
+    pub key_identifier: KeyIdentifier,
+}
+
+impl AuxSecurityHeader {
+    /// Decodes the header from the start of `bytes`
+    ///
+    /// Returns the header and the number of bytes it takes up.
+    pub fn decode(bytes: &[u8]) -> Result<(Self, usize), byte::Error> {
+        if bytes.len() < 5 {
+            return Err(byte::Error::Incomplete);
+        }
+
+        let control        = bytes[0];
+        let security_level = control & 0b111;
+        let key_id_mode    = (control >> 3) & 0b11;
+
+        let mut counter = [0; 4];
+        counter.copy_from_slice(&bytes[1..5]);
+        let frame_counter = u32::from_le_bytes(counter);
+
+        let rest = &bytes[5..];
+        let (key_identifier, key_len) = match key_id_mode {
+            0 => {
+                (KeyIdentifier::Implicit, 0)
+            }
+            1 => {
+                if rest.is_empty() {
+                    return Err(byte::Error::Incomplete);
+                }
+                (KeyIdentifier::Index(rest[0]), 1)
+            }
+            2 => {
+                if rest.len() < 5 {
+                    return Err(byte::Error::Incomplete);
+                }
+                let mut source = [0; 4];
+                source.copy_from_slice(&rest[..4]);
+                (KeyIdentifier::Source4 { source, index: rest[4] }, 5)
+            }
+            _ => {
+                if rest.len() < 9 {
+                    return Err(byte::Error::Incomplete);
+                }
+                let mut source = [0; 8];
+                source.copy_from_slice(&rest[..8]);
+                (KeyIdentifier::Source8 { source, index: rest[8] }, 9)
+            }
+        };
+
+        let header = AuxSecurityHeader {
+            security_level,
+            frame_counter,
+            key_identifier,
+        };
+
+        Ok((header, 5 + key_len))
+    }
+
+    /// Encodes the header into the start of `bytes`
+    ///
+    /// Returns the number of bytes written.
+    pub fn encode(&self, bytes: &mut [u8]) -> Result<usize, byte::Error> {
+        let len = self.encoded_len();
+        if bytes.len() < len {
+            return Err(byte::Error::Incomplete);
+        }
+
+        let key_id_mode = match self.key_identifier {
+            KeyIdentifier::Implicit       => 0,
+            KeyIdentifier::Index(_)       => 1,
+            KeyIdentifier::Source4 { .. } => 2,
+            KeyIdentifier::Source8 { .. } => 3,
+        };
+
+        bytes[0] = (self.security_level & 0b111) | key_id_mode << 3;
+        bytes[1..5].copy_from_slice(&self.frame_counter.to_le_bytes());
+
+        match self.key_identifier {
+            KeyIdentifier::Implicit => {}
+            KeyIdentifier::Index(index) => {
+                bytes[5] = index;
+            }
+            KeyIdentifier::Source4 { source, index } => {
+                bytes[5..9].copy_from_slice(&source);
+                bytes[9] = index;
+            }
+            KeyIdentifier::Source8 { source, index } => {
+                bytes[5..13].copy_from_slice(&source);
+                bytes[13] = index;
+            }
+        }
+
+        Ok(len)
+    }
+
+    /// Returns the length of the encoded header in bytes
+    pub fn encoded_len(&self) -> usize {
+        5 + match self.key_identifier {
+            KeyIdentifier::Implicit       => 0,
+            KeyIdentifier::Index(_)       => 1,
+            KeyIdentifier::Source4 { .. } => 5,
+            KeyIdentifier::Source8 { .. } => 9,
+        }
+    }
+}
+
+
+/// The key identifier of an auxiliary security header
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyIdentifier {
+    /// The key is determined implicitly (key identifier mode 0)
+    Implicit,
+
+    /// The key is determined by a key index (key identifier mode 1)
+    Index(u8),
+
+    /// The key is determined by a 4-byte key source and a key index (key
+    /// identifier mode 2)
+    Source4 {
+        /// The key source
+        source: [u8; 4],
+
+        /// The key index
+        index: u8,
+    },
+
+    /// The key is determined by an 8-byte key source and a key index (key
+    /// identifier mode 3)
+    Source8 {
+        /// The key source
+        source: [u8; 8],
+
+        /// The key index
+        index: u8,
+    },
+}
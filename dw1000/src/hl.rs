@@ -19,23 +19,38 @@ use byte::BytesExt as _;
 use embedded_hal::{
     blocking::spi,
     digital::v2::OutputPin,
+    timer::CountDown,
 };
 use ieee802154::mac::FooterMode;
 use nb;
 use ssmarshal;
 
+#[cfg(feature = "eh1")]
+use crate::compat;
 use crate::{
     ll,
     mac,
+    frame::{
+        self,
+        AuxSecurityHeader,
+        FrameBuilder,
+    },
     time::{
+        self,
         Duration,
         Instant,
     },
     configs::{
+        AonConfig,
+        InitConfig,
         TxConfig,
         RxConfig,
         SfdSequence,
         BitRate,
+        PulseRepetitionFrequency,
+        PreambleLength,
+        PhrMode,
+        UwbChannel,
     },
 };
 
@@ -44,6 +59,15 @@ pub struct DW1000<SPI, CS, State> {
     ll:    ll::DW1000<SPI, CS>,
     seq:   Wrapping<u8>,
     state: State,
+
+    /// Per-channel RX and TX antenna delays, indexed by channel number
+    antenna_delays: [Option<(u16, u16)>; 8],
+
+    /// Whether the receiver needs to be reset before the next receive
+    rx_needs_reset: bool,
+
+    /// The smart TX power settings, saved while smart TX power is disabled
+    smart_tx_power: Option<u32>,
 }
 
 impl<SPI, CS> DW1000<SPI, CS, Uninitialized>
@@ -64,6 +88,10 @@ impl<SPI, CS> DW1000<SPI, CS, Uninitialized>
         DW1000 {
             ll:    ll::DW1000::new(spi, chip_select),
             seq:   Wrapping(0),
+
+            antenna_delays: [None; 8],
+            rx_needs_reset: false,
+            smart_tx_power: None,
             state: Uninitialized,
         }
     }
@@ -78,7 +106,20 @@ impl<SPI, CS> DW1000<SPI, CS, Uninitialized>
     /// Please note that this method assumes that you kept the default
     /// configuration. It is generally recommended not to change configuration
     /// before calling this method.
-    pub fn init(mut self) -> Result<DW1000<SPI, CS, Ready>, Error<SPI, CS>> {
+    pub fn init(self) -> Result<DW1000<SPI, CS, Ready>, Error<SPI, CS>> {
+        self.init_with_config(InitConfig::default())
+    }
+
+    /// Initialize the DW1000, using the provided configuration
+    ///
+    /// Works like [`DW1000::init`], but allows to skip parts of the
+    /// initialization that aren't needed by every application. See
+    /// [`InitConfig`] for details.
+    pub fn init_with_config(mut self, config: InitConfig)
+        -> Result<DW1000<SPI, CS, Ready>, Error<SPI, CS>>
+    {
+        self.check_device_id()?;
+
         // Set AGC_TUNE1. See user manual, section 2.5.5.1.
         self.ll.agc_tune1().write(|w| w.value(0x8870))?;
 
@@ -113,39 +154,84 @@ impl<SPI, CS> DW1000<SPI, CS, Uninitialized>
         self.ll.fs_plltune().write(|w| w.value(0xBE))?;
 
         // Set LDELOAD. See user manual, section 2.5.5.10.
-        self.ll.pmsc_ctrl0().modify(|_, w| w.sysclks(0b01))?;
-        self.ll.otp_ctrl().modify(|_, w| w.ldeload(0b1))?;
-        while self.ll.otp_ctrl().read()?.ldeload() == 0b1 {}
-        self.ll.pmsc_ctrl0().modify(|_, w| w.sysclks(0b00))?;
+        if config.load_lde {
+            self.ll.pmsc_ctrl0().modify(|_, w| w.sysclks(0b01))?;
+            self.ll.otp_ctrl().modify(|_, w| w.ldeload(0b1))?;
+            while self.ll.otp_ctrl().read()?.ldeload() == 0b1 {}
+            self.ll.pmsc_ctrl0().modify(|_, w| w.sysclks(0b00))?;
+        }
+        else {
+            // Without the microcode, the LDE must not run. See user manual,
+            // section 7.2.50.2.
+            self.ll.pmsc_ctrl1().modify(|_, w| w.lderune(0b0))?;
+        }
 
         // Set LDOTUNE. See user manual, section 2.5.5.11.
-        self.ll.otp_addr().write(|w| w.value(0x004))?;
-        self.ll.otp_ctrl().modify(|_, w|
-            w
-                .otprden(0b1)
-                .otpread(0b1)
-        )?;
-        while self.ll.otp_ctrl().read()?.otpread() == 0b1 {}
-        let ldotune_low = self.ll.otp_rdat().read()?.value();
-        if ldotune_low != 0 {
-            self.ll.otp_addr().write(|w| w.value(0x005))?;
-            self.ll.otp_ctrl().modify(|_, w|
-                w
-                    .otprden(0b1)
-                    .otpread(0b1)
-            )?;
-            while self.ll.otp_ctrl().read()?.otpread() == 0b1 {}
-            let ldotune_high = self.ll.otp_rdat().read()?.value();
+        self.load_ldotune()?;
 
-            let ldotune = ldotune_low as u64 | (ldotune_high as u64) << 32;
-            self.ll.ldotune().write(|w| w.value(ldotune))?;
-        }
+        let factory_antenna_delay = match config.factory_antenna_delay {
+            Some((channel, prf)) =>
+                self.read_factory_antenna_delay(channel, prf)?,
+            None =>
+                None,
+        };
 
-        Ok(DW1000 {
+        let mut dw1000 = DW1000 {
             ll:    self.ll,
             seq:   self.seq,
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
             state: Ready,
-        })
+        };
+
+        let antenna_delay = factory_antenna_delay
+            .map(|delay| (delay, delay))
+            .or(config.antenna_delay);
+        if let Some((rx_delay, tx_delay)) = antenna_delay {
+            dw1000.set_antenna_delay(rx_delay, tx_delay)?;
+        }
+
+        Ok(dw1000)
+    }
+
+    /// Initializes the DW1000 and restores a configuration snapshot
+    ///
+    /// Calls [`DW1000::init`], then applies the configuration read earlier
+    /// using [`DW1000::read_config`]. Together with [`DW1000::reset`], this
+    /// can be used to recover from an unexpected state, without losing the
+    /// node configuration.
+    pub fn restore_config(self, config: &Config)
+        -> Result<DW1000<SPI, CS, Ready>, Error<SPI, CS>>
+    {
+        let mut dw1000 = self.init()?;
+
+        dw1000.set_address(config.pan_id, config.short_address)?;
+        dw1000.set_antenna_delay(
+            config.rx_antenna_delay,
+            config.tx_antenna_delay,
+        )?;
+        dw1000.set_xtal_trim(config.xtal_trim)?;
+
+        Ok(dw1000)
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<T> DW1000<compat::Device<T>, compat::NoChipSelect, Uninitialized>
+    where
+        T: embedded_hal_1::spi::SpiDevice<u8>,
+{
+    /// Create a new instance of `DW1000` from an `embedded-hal` 1.0 `SpiDevice`
+    ///
+    /// Unlike [`DW1000::new`], this doesn't require a chip select pin, as the
+    /// `SpiDevice` manages chip select itself. This makes it possible to share
+    /// the SPI bus with other peripherals.
+    ///
+    /// Only available, if the `eh1` feature is enabled.
+    pub fn new_from_spi_device(spi: T) -> Self {
+        DW1000::new(compat::Device(spi), compat::NoChipSelect)
     }
 }
 
@@ -168,21 +254,161 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
         Ok(())
     }
 
+    /// Loads all factory calibration values from OTP and applies them
+    ///
+    /// This applies the crystal trim (see [`DW1000::load_factory_xtal_trim`]),
+    /// the LDOTUNE value (which [`DW1000::init`] already applies), and the
+    /// antenna delay.
+    ///
+    /// Decawave only calibrates the antenna delay for channels 2 and 5, so it
+    /// is only applied for those channels, and only if a value was programmed
+    /// into OTP. The OTP value is used for both the RX and TX antenna delay.
+    pub fn load_factory_calibration(&mut self,
+        channel: UwbChannel,
+        prf:     PulseRepetitionFrequency,
+    )
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.load_factory_xtal_trim()?;
+        self.load_ldotune()?;
+
+        if let Some(delay) = self.read_factory_antenna_delay(channel, prf)? {
+            self.set_antenna_delay(delay, delay)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the RX and TX antenna delays for a specific channel
+    ///
+    /// Antenna delay is frequency-dependent, so a single value is not correct
+    /// for all channels. Delays set with this method are applied by
+    /// [`DW1000::send`] and [`DW1000::receive`] whenever they configure the
+    /// given channel, overriding the value set via
+    /// [`DW1000::set_antenna_delay`]. Channels without a specific value keep
+    /// using whatever antenna delay is currently configured.
+    pub fn set_antenna_delay_for_channel(&mut self,
+        channel:  UwbChannel,
+        rx_delay: u16,
+        tx_delay: u16,
+    ) {
+        self.antenna_delays[channel as usize] = Some((rx_delay, tx_delay));
+    }
+
     /// Sets the network id and address used for sending and receiving
     pub fn set_address(&mut self, pan_id: mac::PanId, addr: mac::ShortAddress)
         -> Result<(), Error<SPI, CS>>
+    {
+        self.set_pan_address(PanAddress { pan_id, short_addr: addr })
+    }
+
+    /// Sets the network id and address used for sending and receiving
+    ///
+    /// Works like [`DW1000::set_address`], but takes both values as a single
+    /// [`PanAddress`], so they can't be swapped accidentally.
+    pub fn set_pan_address(&mut self, address: PanAddress)
+        -> Result<(), Error<SPI, CS>>
     {
         self.ll
             .panadr()
             .write(|w|
                 w
-                    .pan_id(pan_id.0)
-                    .short_addr(addr.0)
+                    .pan_id(address.pan_id.0)
+                    .short_addr(address.short_addr.0)
+            )?;
+
+        Ok(())
+    }
+
+    /// Sets the network id, leaving the short address unchanged
+    pub fn set_pan_id(&mut self, pan_id: mac::PanId)
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.ll
+            .panadr()
+            .modify(|_, w| w.pan_id(pan_id.0))?;
+
+        Ok(())
+    }
+
+    /// Sets the short address, leaving the network id unchanged
+    pub fn set_short_address(&mut self, addr: mac::ShortAddress)
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.ll
+            .panadr()
+            .modify(|_, w| w.short_addr(addr.0))?;
+
+        Ok(())
+    }
+
+    /// Configures whether this node acts as a PAN coordinator
+    ///
+    /// Sets the FFBC bit in SYS_CFG and makes sure frame filtering is enabled.
+    /// Normally, the frame filter rejects frames without a destination
+    /// address. If this node is a coordinator, data and MAC command frames
+    /// that only have a source address are accepted, as long as their source
+    /// PAN ID matches our own (IEEE 802.15.4-2011, section 5.1.6.2).
+    ///
+    /// Please note that [`DW1000::receive`] disables frame filtering, if
+    /// `frame_filtering` is `false` in the [`RxConfig`]. The coordinator
+    /// setting is retained, but has no effect while filtering is disabled.
+    pub fn set_pan_coordinator(&mut self, is_coordinator: bool)
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.ll
+            .sys_cfg()
+            .modify(|_, w|
+                w
+                    .ffen(0b1)
+                    .ffbc(is_coordinator as u8)
             )?;
 
         Ok(())
     }
 
+    /// Sets the offset into TX_BUFFER that the transmitter starts reading from
+    ///
+    /// This sets the TXBOFFS field of TX_FCTRL. Together with
+    /// [`ll::DW1000::write_tx_buffer_at`], this allows you to assemble frames
+    /// in different regions of the transmit buffer, without rewriting the
+    /// whole buffer for every transmission.
+    ///
+    /// The transmitter sends TFLEN bytes (as configured in TX_FCTRL), starting
+    /// at the offset. Since TFLEN includes the two-octet FCS, which is added by
+    /// the hardware, the frame data must occupy `offset` to
+    /// `offset + TFLEN - 2`.
+    ///
+    /// Please note that [`DW1000::send`] always writes its frame to the start
+    /// of the buffer and resets the offset to 0. Use [`DW1000::send_raw`] to
+    /// send a frame from a different offset.
+    ///
+    /// Returns [`Error::TxBufferOverflow`], if `offset` is outside of the
+    /// buffer.
+    pub fn set_tx_buffer_offset(&mut self, offset: u16)
+        -> Result<(), Error<SPI, CS>>
+    {
+        if offset as usize >= ll::TX_BUFFER_CAPACITY {
+            return Err(Error::TxBufferOverflow);
+        }
+
+        self.ll
+            .tx_fctrl()
+            .modify(|_, w| w.txboffs(offset))?;
+
+        Ok(())
+    }
+
+    /// Returns the sequence number that the next call to `send` will use
+    ///
+    /// [`DW1000::send`] uses an internal counter for the sequence number of
+    /// the frames it sends, and increments it with every frame. This can be
+    /// used to keep track of which frame was sent with which sequence number,
+    /// for example to match acknowledgements.
+    pub fn next_seq(&self) -> u8 {
+        self.seq.0
+    }
+
     /// Send an IEEE 802.15.4 MAC frame
     ///
     /// The `data` argument is wrapped into an IEEE 802.15.4 MAC frame and sent
@@ -201,6 +427,11 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
     /// It consumes this instance of `DW1000` and returns another instance which
     /// is in the `Sending` state, and can be used to wait for the transmission
     /// to finish and check its result.
+    ///
+    /// Returns [`Error::PllNotLocked`], if the clock PLL or the RF PLL has lost
+    /// its lock, instead of attempting a transmission that would likely fail.
+    /// The losing lock flags are cleared in that case, so sending can be
+    /// retried once the PLL has locked again.
     pub fn send(mut self,
         data:         &[u8],
         destination:  Option<mac::Address>,
@@ -209,6 +440,227 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
     )
         -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
     {
+        let seq = self.seq.0;
+        self.seq += Wrapping(1);
+
+        let frame = FrameBuilder::new(data)
+            .version(config.frame_version)
+            .destination(destination)
+            .source(Some(self.get_address()?))
+            .seq(seq)
+            .build();
+
+        self.send_frame(frame, delayed_time, config)
+    }
+
+    /// Send a reply a fixed time after a frame was received
+    ///
+    /// Schedules a delayed transmission, so that the frame's RMARKER leaves the
+    /// antenna `delay` after `rx_time`, which should be the RX timestamp of the
+    /// received frame. Since the transmitter adds the TX antenna delay to the
+    /// time in DX_TIME, it is subtracted here. This is the turnaround
+    /// responders in two-way ranging protocols need.
+    ///
+    /// Please note that the DW1000 ignores the low 9 bits of DX_TIME, so the
+    /// actual transmission time can be up to roughly 8 ns earlier than
+    /// requested.
+    pub fn reply_after(mut self,
+        data:        &[u8],
+        destination: Option<mac::Address>,
+        rx_time:     Instant,
+        delay:       Duration,
+        config:      TxConfig,
+    )
+        -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
+    {
+        // Make sure the delay for this channel is in effect, before reading it.
+        self.apply_antenna_delay_for_channel(config.channel)?;
+        let tx_antenna_delay = self.get_tx_antenna_delay()?;
+        let tx_time = rx_time + delay - tx_antenna_delay;
+
+        self.send(data, destination, Some(tx_time), config)
+    }
+
+    /// Send a frame as part of a periodic schedule
+    ///
+    /// Schedules a delayed transmission, so that the frame's RMARKER leaves the
+    /// antenna `period` after `last_tx_time`, which should be the TX timestamp
+    /// of the previous transmission in the schedule (see [`DW1000::tx_time`]).
+    /// Like [`DW1000::reply_after`], this subtracts the TX antenna delay.
+    ///
+    /// Since the schedule is based on the DW1000's clock, the period is exact,
+    /// as long as it is a multiple of 512 time units (see
+    /// [`time::next_periodic_tx`]).
+    ///
+    /// [`time::next_periodic_tx`]: ../time/fn.next_periodic_tx.html
+    pub fn send_periodic(mut self,
+        data:         &[u8],
+        destination:  Option<mac::Address>,
+        last_tx_time: Instant,
+        period:       Duration,
+        config:       TxConfig,
+    )
+        -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
+    {
+        // Make sure the delay for this channel is in effect, before reading it.
+        self.apply_antenna_delay_for_channel(config.channel)?;
+        let tx_antenna_delay = self.get_tx_antenna_delay()?;
+        let tx_time =
+            time::next_periodic_tx(last_tx_time, period) - tx_antenna_delay;
+
+        self.send(data, destination, Some(tx_time), config)
+    }
+
+    /// Send a frame and return its TX timestamp
+    ///
+    /// Works like [`DW1000::send`], but busily waits for the transmission to
+    /// finish, then reads the TX timestamp (see [`DW1000::tx_time`]). This is
+    /// the common pattern for ranging initiators, and makes sure the timestamp
+    /// is read before any other operation can overwrite it.
+    ///
+    /// Returns this instance in the `Ready` state, together with the TX
+    /// timestamp. See [`SendError`] for what is returned if an error occurs.
+    pub fn send_and_get_tx_time(self,
+        data:         &[u8],
+        destination:  Option<mac::Address>,
+        delayed_time: Option<Instant>,
+        config:       TxConfig,
+    )
+        -> Result<(Self, Instant), SendError<SPI, CS>>
+    {
+        let mut sending = self.send(data, destination, delayed_time, config)
+            .map_err(SendError::Start)?;
+
+        // Can't use `map_err` and `?` here, as the compiler will complain
+        // about `sending` moving into the closure.
+        match nb::block!(sending.wait()) {
+            Ok(())     => (),
+            Err(error) => return Err(SendError::Send(sending, error)),
+        }
+        // `wait` has returned successfully, so the following should never
+        // panic.
+        let tx_time = sending.tx_time().unwrap();
+
+        let ready = sending.finish_sending()
+            .map_err(|(sending, error)| SendError::Send(sending, error))?;
+
+        Ok((ready, tx_time))
+    }
+
+    /// Send an acknowledgement for a received frame
+    ///
+    /// Builds an ACK frame with the sequence number of `message` and sends it.
+    /// This can be used to acknowledge frames in software, if automatic
+    /// acknowledgement is not used. See [`Message::needs_ack`].
+    ///
+    /// If `delayed_time` is given, the ACK is sent at that time, which can be
+    /// used to implement a defined turnaround time.
+    pub fn send_ack(self,
+        message:      &Message,
+        delayed_time: Option<Instant>,
+        config:       TxConfig,
+    )
+        -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
+    {
+        let frame = FrameBuilder::new(&[])
+            .frame_type(mac::FrameType::Acknowledgement)
+            .seq(message.frame.header.seq)
+            .build();
+
+        self.send_frame(frame, delayed_time, config)
+    }
+
+    /// Send a pre-built IEEE 802.15.4 MAC frame
+    ///
+    /// Works like [`DW1000::send`], except that the frame is sent as-is. This
+    /// gives full control over the MAC header. [`FrameBuilder`] can be used to
+    /// create the frame.
+    ///
+    /// Please note that the frame's sequence number is not managed by this
+    /// method.
+    pub fn send_frame(mut self,
+        frame:        mac::Frame,
+        delayed_time: Option<Instant>,
+        config:       TxConfig,
+    )
+        -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
+    {
+        // Frames that don't fit into a standard-length frame can't be encoded
+        // here. `send_raw` can be used to send longer frames.
+        let mut buffer = [0; 127];
+        let mut len    = 0;
+        buffer.write_with(&mut len, frame, FooterMode::None)
+            .map_err(Error::Frame)?;
+
+        self.ll.write_tx_buffer_at(0, &buffer[..len])?;
+
+        self.start_sending(0, len, delayed_time, config)
+    }
+
+    /// Send data that has already been written to the transmit buffer
+    ///
+    /// Sends `len` bytes, starting at `offset` in TX_BUFFER, as they are. The
+    /// data needs to be written beforehand, using
+    /// [`ll::DW1000::write_tx_buffer_at`], and must already contain the MAC
+    /// header. Unless [`TxConfig::suppress_fcs`] is set, the DW1000 appends the
+    /// two-octet FCS, which needs to fit into the buffer too. This sets the
+    /// TXBOFFS and TFLEN fields of TX_FCTRL, so frames can be prepared in
+    /// different regions of the buffer and sent without copying them. See user
+    /// manual, section 7.2.10.
+    ///
+    /// Frames longer than 127 bytes can be sent in [`PhrMode::Extended`] (see
+    /// [`DW1000::set_phr_mode`]).
+    ///
+    /// Otherwise works like [`DW1000::send_frame`]. Returns
+    /// [`Error::TxBufferOverflow`], if the frame extends beyond the end of the
+    /// buffer, and [`Error::FrameTooLong`], if it is too long for the
+    /// configured PHR mode.
+    pub fn send_raw(self,
+        offset:       u16,
+        len:          usize,
+        delayed_time: Option<Instant>,
+        config:       TxConfig,
+    )
+        -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
+    {
+        self.start_sending(offset, len, delayed_time, config)
+    }
+
+    /// Starts sending `len` bytes from `offset` in the transmit buffer
+    fn start_sending(mut self,
+        offset:       u16,
+        len:          usize,
+        delayed_time: Option<Instant>,
+        config:       TxConfig,
+    )
+        -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
+    {
+        // Unless the FCS is suppressed, the DW1000 appends it to the data.
+        let tflen = if config.suppress_fcs { len } else { len + 2 };
+        if offset as usize + tflen > ll::TX_BUFFER_CAPACITY {
+            return Err(Error::TxBufferOverflow);
+        }
+
+        // Frames longer than 127 bytes require the extended PHR mode. See user
+        // manual, section 7.2.10.
+        let max_len = match self.ll.sys_cfg().read()?.phr_mode() {
+            0b00 => 127,
+            _    => 1023,
+        };
+        if tflen > max_len {
+            return Err(Error::FrameTooLong);
+        }
+
+        // A transmission started while a PLL is unlocked fails silently or
+        // produces a corrupted frame. The losing lock flags are sticky, so
+        // clear them after reporting the problem. If the PLL has recovered,
+        // the next attempt can succeed.
+        let sys_status = self.ll.sys_status().read()?;
+        if sys_status.clkpll_ll() == 0b1 || sys_status.rfpll_ll() == 0b1 {
+            self.ll.sys_status().write(|w| w.clkpll_ll(0b1).rfpll_ll(0b1))?;
+            return Err(Error::PllNotLocked);
+        }
+
         // Clear event counters
         self.ll.evc_ctrl().write(|w| w.evc_clr(0b1))?;
         while self.ll.evc_ctrl().read()?.evc_clr() == 0b1 {}
@@ -224,25 +676,19 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
         // doesn't happen.
         self.force_idle()?;
 
-        let seq = self.seq.0;
-        self.seq += Wrapping(1);
-
-        let frame = mac::Frame {
-            header: mac::Header {
-                frame_type:      mac::FrameType::Data,
-                version:         mac::FrameVersion::Ieee802154_2006,
-                security:        mac::Security::None,
-                frame_pending:   false,
-                ack_request:     false,
-                pan_id_compress: false,
-                destination:     destination,
-                source:          Some(self.get_address()?),
-                seq:             seq,
-            },
-            content: mac::FrameContent::Data,
-            payload: data,
-            footer: [0; 2],
-        };
+        // Clear any stale transmit status. If automatic acknowledgement is
+        // enabled, sending an ACK frame sets the same flags as a regular
+        // transmission, which would cause `wait` to return too early.
+        self.ll
+            .sys_status()
+            .write(|w|
+                w
+                    .aat(0b1)   // Automatic Acknowledge Trigger
+                    .txfrb(0b1) // Transmit Frame Begins
+                    .txprs(0b1) // Transmit Preamble Sent
+                    .txphs(0b1) // Transmit PHY Header Sent
+                    .txfrs(0b1) // Transmit Frame Sent
+            )?;
 
         delayed_time.map(|time| {
             self.ll
@@ -253,27 +699,13 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
         });
 
         // Prepare transmitter
-        let mut len = 0;
-        self.ll
-            .tx_buffer()
-            .write(|w| {
-                let result =
-                    w.data().write_with(&mut len, frame, FooterMode::None);
-
-                if let Err(err) = result {
-                    panic!("Failed to write frame: {:?}", err);
-                }
-
-                w
-            })?;
         self.ll
             .tx_fctrl()
             .modify(|_, w| {
-                let tflen = len as u8 + 2;
                 w
-                    .tflen(tflen) // data length + two-octet CRC
-                    .tfle(0)      // no non-standard length extension
-                    .txboffs(0)   // no offset in TX_BUFFER
+                    .tflen((tflen & 0x7f) as u8) // frame length, including FCS
+                    .tfle((tflen >> 7) as u8) // length extension (extended PHR mode)
+                    .txboffs(offset) // offset of the frame in TX_BUFFER
                     .txbr(config.bitrate as u8) // configured bitrate
                     .tr(config.ranging_enable as u8) // configured ranging bit
                     .txprf(config.pulse_repetition_frequency as u8) // configured PRF
@@ -281,6 +713,13 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
                     .pe((config.preamble_length as u8) & 0b0011) // last two bits of configured preamble length
             })?;
 
+        // Smart TX power control must only be used for frames shorter than
+        // 1 ms. See user manual, section 6.2.
+        let smart_tx_power = config.smart_tx_power.unwrap_or_else(||
+            config.frame_duration_ps(tflen) < 1_000_000_000
+        );
+        self.set_smart_tx_power(smart_tx_power)?;
+
         // Set the channel and sfd settings
         self.ll
             .chan_ctrl()
@@ -303,6 +742,8 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
             SfdSequence::User => {}, // Users are responsible for setting the lengths themselves
         }
 
+        self.apply_antenna_delay_for_channel(config.channel)?;
+
         // Tune for the correct channel
         self.ll.rf_txctrl().write(|w| w.value(config.channel.get_recommended_rf_txctrl()))?;
         self.ll.tc_pgdelay().write(|w| w.value(config.channel.get_recommended_tc_pgdelay()))?;
@@ -311,18 +752,26 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
 
         // Todo: Power control (register 0x1E)
 
-        // Start transmission
+        // Start transmission. FCS suppression is set or cancelled explicitly,
+        // so a previous setting doesn't carry over. See user manual, section
+        // 7.2.15.
         self.ll
             .sys_ctrl()
             .modify(|_, w|
                 if delayed_time.is_some() { w.txdlys(0b1) } else { w }
+                    .sfcst(config.suppress_fcs as u8)
+                    .cansfcs(!config.suppress_fcs as u8)
                     .txstrt(0b1)
             )?;
 
         Ok(DW1000 {
             ll:    self.ll,
             seq:   self.seq,
-            state: Sending { finished: false },
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
+            state: Sending { finished: false, tx_time: None },
         })
     }
 
@@ -336,23 +785,49 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
     /// and more. Make sure that the values used are the same as of the frames
     /// that are transmitted. The default works with the TxConfig's default and
     /// is a sane starting point.
-    pub fn receive(mut self, config: RxConfig)
+    pub fn receive(self, config: RxConfig)
+        -> Result<DW1000<SPI, CS, Receiving>, Error<SPI, CS>>
+    {
+        self.start_receiving(config, None)
+    }
+
+    /// Attempt to receive an IEEE 802.15.4 MAC frame, starting at a given time
+    ///
+    /// Works like [`DW1000::receive`], except that the receiver is only
+    /// enabled once the system time reaches `start`. This can be used to
+    /// guarantee a minimum gap after a previous operation, for example by
+    /// passing the RX time of the last frame plus the required gap.
+    ///
+    /// The DW1000 has no dedicated setting for delaying the re-enabling of the
+    /// receiver. The automatic re-enable (SYS_CFG.RXAUTR) takes effect
+    /// immediately, and the inter-frame spacing (TX_FCTRL.IFSDELAY) only
+    /// affects transmissions. This method writes `start` to DX_TIME (register
+    /// 0x0A), then sets RXDLYE and RXENAB in SYS_CTRL (register 0x0D). See
+    /// user manual, sections 7.2.12 and 7.2.15.
+    ///
+    /// Please note that the DW1000 ignores the low 9 bits of DX_TIME, so the
+    /// receiver can be enabled up to roughly 8 ns earlier than requested.
+    pub fn receive_delayed(self, start: Instant, config: RxConfig)
+        -> Result<DW1000<SPI, CS, Receiving>, Error<SPI, CS>>
+    {
+        self.start_receiving(config, Some(start))
+    }
+
+    fn start_receiving(mut self,
+        config:       RxConfig,
+        delayed_time: Option<Instant>,
+    )
         -> Result<DW1000<SPI, CS, Receiving>, Error<SPI, CS>>
     {
         // For unknown reasons, the DW1000 gets stuck in RX mode without ever
-        // receiving anything, after receiving one good frame. Reset the
-        // receiver to make sure its in a valid state before attempting to
-        // receive anything.
-        self.ll
-            .pmsc_ctrl0()
-            .modify(|_, w|
-                w.softreset(0b1110) // reset receiver
-            )?;
-        self.ll
-            .pmsc_ctrl0()
-            .modify(|_, w|
-                w.softreset(0b1111) // clear reset
-            )?;
+        // receiving anything, after receiving one good frame. If the previous
+        // receive operation left it in that state (see `finish_receiving`),
+        // reset the receiver to make sure its in a valid state before
+        // attempting to receive anything.
+        if self.rx_needs_reset {
+            self.reset_receiver()?;
+            self.rx_needs_reset = false;
+        }
 
         // We're already resetting the receiver in the previous step, and that's
         // good enough to make my example program that's both sending and
@@ -401,11 +876,14 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
         // Now that PLLLDT is set, clear all bits in SYS_STATUS that depend on
         // it for reliable operation. After that is done, these bits should work
         // reliably.
+        //
+        // CPLOCK is left alone. It is only set when the clock PLL locks, so
+        // clearing it here would make the PLL look unlocked (see
+        // `pll_status`) until it locks again.
         self.ll
             .sys_status()
             .write(|w|
                 w
-                    .cplock(0b1)
                     .clkpll_ll(0b1)
             )?;
 
@@ -429,14 +907,43 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
             SfdSequence::User => {}, // Users are responsible for setting the lengths themselves
         }
 
+        // Set PRF-dependent tuning
+        self.ll.agc_tune1().write(|w| w.value(config.pulse_repetition_frequency.get_recommended_agc_tune1()))?;
+        self.ll.lde_cfg2().write(|w| w.value(config.pulse_repetition_frequency.get_recommended_lde_cfg2()))?;
+
         // Set general tuning
-        self.ll.drx_tune0b().write(|w| w.value(config.bitrate.get_recommended_drx_tune0b(config.sfd_sequence)))?;
-        self.ll.drx_tune1a().write(|w| w.value(config.pulse_repetition_frequency.get_recommended_drx_tune1a()))?;
-        let drx_tune1b = config.expected_preamble_length.get_recommended_drx_tune1b(config.bitrate)?;
-        self.ll.drx_tune1b().write(|w| w.value(drx_tune1b))?;
-        let drx_tune2 = config.pulse_repetition_frequency.get_recommended_drx_tune2(config.expected_preamble_length.get_recommended_pac_size())?;
-        self.ll.drx_tune2().write(|w| w.value(drx_tune2))?;
-        self.ll.drx_tune4h().write(|w| w.value(config.expected_preamble_length.get_recommended_dxr_tune4h()))?;
+        let pac_size = self.apply_drx_config(&config)?;
+
+        // Set preamble detection timeout. The register counts in units of PAC
+        // size. See user manual, section 7.2.40.9.
+        let pretoc = match config.preamble_timeout {
+            Some(timeout) => {
+                let pac_duration =
+                    config.pulse_repetition_frequency
+                        .get_preamble_symbol_duration()
+                    * pac_size as u64;
+                let count = timeout.value() / pac_duration;
+                if count == 0 || count > u16::MAX as u64 {
+                    return Err(Error::InvalidConfiguration);
+                }
+                count as u16
+            }
+            None => 0,
+        };
+        self.ll.drx_pretoc().write(|w| w.count(pretoc))?;
+
+        // Set SFD detection timeout. A value of zero must not be used. See user
+        // manual, section 7.2.40.7.
+        let sfd_timeout = match config.sfd_timeout {
+            Some(symbols) => symbols,
+            None          => config.get_recommended_sfd_timeout(),
+        };
+        if sfd_timeout == 0 {
+            return Err(Error::InvalidConfiguration);
+        }
+        self.ll.drx_sfdtoc().write(|w| w.count(sfd_timeout))?;
+
+        self.apply_antenna_delay_for_channel(config.channel)?;
 
         // Set channel tuning
         self.ll.rf_rxctrlh().write(|w| w.value(config.channel.get_recommended_rf_rxctrlh()))?;
@@ -446,20 +953,355 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
         // Set the rx bitrate
         self.ll.sys_cfg().modify(|_, w| w.rxm110k((config.bitrate == BitRate::Kbps110) as u8))?;
 
+        // If the LDE was disabled during initialization, there's no point in
+        // waiting for it when a frame is received.
+        let lde_enabled = self.ll.pmsc_ctrl1().read()?.lderune() == 0b1;
+
+        if let Some(time) = delayed_time {
+            self.ll.dx_time().write(|w| w.value(time.value()))?;
+        }
+
         self.ll
             .sys_ctrl()
             .modify(|_, w|
-                w.rxenab(0b1)
+                w
+                    .rxenab(0b1)
+                    .rxdlye(delayed_time.is_some() as u8)
             )?;
 
         Ok(DW1000 {
             ll:    self.ll,
             seq:   self.seq,
-            state: Receiving { finished: false },
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
+            state: Receiving {
+                finished:      false,
+                buffer:        [0; 127],
+                min_frame_len: config.min_frame_len,
+                lde_enabled,
+
+                keep_corrupted_frames: config.keep_corrupted_frames,
+            },
         })
     }
 
-    /// Enables transmit interrupts for the events that `wait` checks
+    /// Attempt to receive any IEEE 802.15.4 MAC frame, ignoring frame filtering
+    ///
+    /// Works like [`DW1000::receive`], but disables frame filtering for this
+    /// receive operation, regardless of [`RxConfig::frame_filtering`]. This is
+    /// useful for diagnostics, for example to check which frames are sent on a
+    /// channel.
+    ///
+    /// Since every call to [`DW1000::receive`] configures frame filtering
+    /// according to its `config`, there's nothing to restore afterwards. Frame
+    /// filtering is enabled again by the next receive operation that requests
+    /// it, even if this one failed.
+    pub fn receive_promiscuous(self, config: RxConfig)
+        -> Result<DW1000<SPI, CS, Receiving>, Error<SPI, CS>>
+    {
+        self.receive(RxConfig { frame_filtering: false, .. config })
+    }
+
+    /// Attempt to receive an IEEE 802.15.4 MAC frame with the given SFD
+    ///
+    /// Works like [`DW1000::receive`], but overrides
+    /// [`RxConfig::sfd_sequence`]. This makes it easy to alternate between
+    /// networks that use different SFD sequences, for example when bridging
+    /// between them.
+    pub fn receive_with_sfd(self, sfd: SfdSequence, config: RxConfig)
+        -> Result<DW1000<SPI, CS, Receiving>, Error<SPI, CS>>
+    {
+        self.receive(RxConfig { sfd_sequence: sfd, .. config })
+    }
+
+    /// Configures the SFD sequence, without touching any other settings
+    ///
+    /// [`DW1000::send`] and [`DW1000::receive`] configure the SFD sequence
+    /// along with everything else. This method only writes the registers that
+    /// depend on the SFD sequence, which makes it cheap enough to call between
+    /// two receive operations:
+    ///
+    /// - The DWSFD, TNSSFD, and RNSSFD bits in CHAN_CTRL
+    /// - SFD_LENGTH, for the Decawave sequences
+    /// - DRX_TUNE0b, which also depends on `bitrate`
+    ///
+    /// See user manual, sections 7.2.32, 7.2.34, and 7.2.40.2.
+    pub fn set_sfd(&mut self, sfd: SfdSequence, bitrate: BitRate)
+        -> Result<(), Error<SPI, CS>>
+    {
+        let decawave = sfd == SfdSequence::Decawave
+            || sfd == SfdSequence::DecawaveAlt;
+        let non_standard = sfd == SfdSequence::User
+            || sfd == SfdSequence::DecawaveAlt;
+
+        self.ll
+            .chan_ctrl()
+            .modify(|_, w|
+                w
+                    .dwsfd(decawave as u8)
+                    .tnssfd(non_standard as u8)
+                    .rnssfd(non_standard as u8)
+            )?;
+
+        match sfd {
+            SfdSequence::Decawave =>
+                self.ll.sfd_length().write(|w| w.value(8))?,
+            SfdSequence::DecawaveAlt =>
+                self.ll.sfd_length().write(|w| w.value(16))?,
+            SfdSequence::IEEE | SfdSequence::User =>
+                (),
+        }
+
+        self.ll
+            .drx_tune0b()
+            .write(|w| w.value(bitrate.get_recommended_drx_tune0b(sfd)))?;
+
+        Ok(())
+    }
+
+    /// Listens on multiple channels in turn
+    ///
+    /// For each channel in `channels`, enables the receiver using `config`,
+    /// with its channel replaced, and listens for the duration of `dwell`, as
+    /// measured by `timer`. `on_frame` is called for every frame received
+    /// during that time, along with the channel it was received on. After
+    /// every frame, the receiver is re-enabled until the dwell time is over.
+    ///
+    /// Receive errors (like [`Error::Fcs`]) don't abort the scan. The receiver
+    /// is re-enabled, and the scan continues. Only SPI errors are returned.
+    /// Once all channels have been scanned, the driver is returned in the
+    /// `Ready` state.
+    pub fn scan_channels<T, F>(mut self,
+        channels:     &[UwbChannel],
+        config:       RxConfig,
+        timer:        &mut T,
+        dwell:        T::Time,
+        buffer:       &mut [u8],
+        mut on_frame: F,
+    )
+        -> Result<Self, Error<SPI, CS>>
+        where
+            T:       CountDown,
+            T::Time: Clone,
+            F:       FnMut(UwbChannel, &Message),
+    {
+        for &channel in channels {
+            let config = RxConfig { channel, .. config };
+            timer.start(dwell.clone());
+
+            loop {
+                let mut receiving = self.receive(config)?;
+
+                let dwell_over = match receiving.wait_timeout(buffer, timer) {
+                    Ok(message) => {
+                        on_frame(channel, &message);
+                        false
+                    }
+                    Err(Error::Timeout) =>
+                        true,
+                    Err(Error::Spi(error)) =>
+                        return Err(Error::Spi(error)),
+                    Err(_) =>
+                        false,
+                };
+
+                self = receiving.finish_receiving()
+                    .map_err(|(_, error)| error)?;
+
+                if dwell_over {
+                    break;
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Enables SNIFF mode, to reduce receiver power consumption
+    ///
+    /// In SNIFF mode, the receiver alternates between listening for a
+    /// preamble for `on_time` preamble symbols, and sleeping for `off_time`.
+    /// Once a preamble is detected, the receiver stays on to receive the
+    /// frame. This reduces power consumption while waiting for frames, at the
+    /// cost of requiring senders to use a longer preamble. See user manual,
+    /// section 7.2.30.
+    ///
+    /// The on-time is configured in units of PACs, so `on_time` is rounded up
+    /// to a multiple of the PAC size from `config`, which should be the same
+    /// configuration that is passed to [`DW1000::receive`]. The off-time is
+    /// configured in units of 128 system clock cycles (roughly 1 µs), and is
+    /// rounded down.
+    ///
+    /// Returns [`Error::InvalidConfiguration`], if `on_time` is zero or longer
+    /// than 15 PACs, or if `off_time` is longer than 255 units (roughly
+    /// 261 µs).
+    ///
+    /// SNIFF mode stays enabled for subsequent receive operations, until it
+    /// is disabled using [`DW1000::disable_sniff_mode`].
+    pub fn enable_sniff_mode(&mut self,
+        on_time:  u16,
+        off_time: Duration,
+        config:   RxConfig,
+    )
+        -> Result<(), Error<SPI, CS>>
+    {
+        let pac_size = config.get_pac_size() as u16;
+        let on_pacs  = on_time.saturating_add(pac_size - 1) / pac_size;
+        if on_pacs == 0 || on_pacs > 0xf {
+            return Err(Error::InvalidConfiguration);
+        }
+
+        // One unit of SNIFF_OFFT is 128 cycles of the 124.8 MHz system clock,
+        // which is 2^16 units of DW1000 time.
+        let off_units = off_time.value() >> 16;
+        if off_units > 0xff {
+            return Err(Error::InvalidConfiguration);
+        }
+
+        self.ll
+            .pmsc_ctrl0()
+            .modify(|_, w| w.pll2_seq_en(0b1))?;
+        self.ll
+            .rx_sniff()
+            .modify(|_, w|
+                w
+                    .sniff_ont(on_pacs as u8)
+                    .sniff_offt(off_units as u8)
+            )?;
+
+        Ok(())
+    }
+
+    /// Disables SNIFF mode
+    ///
+    /// See [`DW1000::enable_sniff_mode`].
+    pub fn disable_sniff_mode(&mut self) -> Result<(), Error<SPI, CS>> {
+        self.ll
+            .rx_sniff()
+            .modify(|_, w|
+                w
+                    .sniff_ont(0)
+                    .sniff_offt(0)
+            )?;
+        self.ll
+            .pmsc_ctrl0()
+            .modify(|_, w| w.pll2_seq_en(0b0))?;
+
+        Ok(())
+    }
+
+    /// Indicates whether the DW1000 is stuck in RX or TX mode
+    ///
+    /// In the `Ready` state, no operation should be ongoing. If SYS_STATE shows
+    /// anything other than an idle transceiver, the DW1000 got stuck, and
+    /// needs to be reset to work reliably again.
+    ///
+    /// Please note that this can't detect a receiver that stopped working
+    /// after a receive operation was finished, as finishing it forces the
+    /// transceiver into IDLE mode. [`DW1000::finish_receiving`] does the same
+    /// check before that, and [`DW1000::receive`] resets the receiver, if it
+    /// was stuck.
+    pub fn is_stuck(&mut self) -> Result<bool, Error<SPI, CS>> {
+        self.transceiver_active()
+    }
+
+    /// Enables or disables smart TX power control
+    ///
+    /// The fields in TX_POWER mean different things, depending on whether
+    /// smart TX power control is enabled (see user manual, section 7.2.31), so
+    /// TX_POWER is converted when switching. When disabling, the smart
+    /// settings are saved, and the normal power setting (BOOSTNORM) is used
+    /// for the whole frame. For the reference values in user manual, tables
+    /// 19 and 20, this yields the recommended manual settings. When enabling,
+    /// the saved smart settings are restored.
+    fn set_smart_tx_power(&mut self, enable: bool)
+        -> Result<(), Error<SPI, CS>>
+    {
+        let enabled = self.ll.sys_cfg().read()?.dis_stxp() == 0b0;
+        if enable == enabled {
+            return Ok(());
+        }
+
+        if enable {
+            if let Some(value) = self.smart_tx_power.take() {
+                self.ll.tx_power().write(|w| w.value(value))?;
+            }
+        }
+        else {
+            let tx_power = self.ll.tx_power().read()?;
+            self.smart_tx_power = Some(tx_power.value());
+
+            let setting = tx_power.boostnorm();
+            self.ll.tx_power().write(|w|
+                w
+                    .boostnorm(setting)
+                    .txpowphr(setting)
+                    .txpowsd(setting)
+                    .boostp125(setting)
+            )?;
+        }
+
+        self.ll.sys_cfg().modify(|_, w| w.dis_stxp(!enable as u8))?;
+
+        Ok(())
+    }
+
+    /// Applies the antenna delays for the channel, if any have been set
+    fn apply_antenna_delay_for_channel(&mut self, channel: UwbChannel)
+        -> Result<(), Error<SPI, CS>>
+    {
+        if let Some((rx_delay, tx_delay)) =
+            self.antenna_delays[channel as usize]
+        {
+            self.set_antenna_delay(rx_delay, tx_delay)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes all DRX tuning registers for the given configuration
+    ///
+    /// DRX_TUNE0b, DRX_TUNE1a, DRX_TUNE1b, DRX_TUNE2, and DRX_TUNE4H depend on
+    /// each other, and reception degrades, if they don't match. To make sure
+    /// the receiver never operates with an inconsistent set, all values are
+    /// computed and validated first, then the DW1000 is forced into IDLE mode
+    /// before any of them are written. The receiver must be re-enabled
+    /// afterwards. See user manual, section 7.2.40.
+    ///
+    /// Returns the PAC size, which other settings depend on.
+    fn apply_drx_config(&mut self, config: &RxConfig)
+        -> Result<u8, Error<SPI, CS>>
+    {
+        let pac_size = config.get_pac_size();
+        if pac_size as u16 >= config.expected_preamble_length.get_symbol_count() {
+            return Err(Error::InvalidConfiguration);
+        }
+
+        let drx_tune0b = config.bitrate
+            .get_recommended_drx_tune0b(config.sfd_sequence);
+        let drx_tune1a = config.pulse_repetition_frequency
+            .get_recommended_drx_tune1a();
+        let drx_tune1b = config.expected_preamble_length
+            .get_recommended_drx_tune1b(config.bitrate)?;
+        let drx_tune2 = config.pulse_repetition_frequency
+            .get_recommended_drx_tune2(pac_size)?;
+        let drx_tune4h = config.expected_preamble_length
+            .get_recommended_dxr_tune4h();
+
+        self.force_idle()?;
+
+        self.ll.drx_tune0b().write(|w| w.value(drx_tune0b))?;
+        self.ll.drx_tune1a().write(|w| w.value(drx_tune1a))?;
+        self.ll.drx_tune1b().write(|w| w.value(drx_tune1b))?;
+        self.ll.drx_tune2().write(|w| w.value(drx_tune2))?;
+        self.ll.drx_tune4h().write(|w| w.value(drx_tune4h))?;
+
+        Ok(pac_size)
+    }
+
+    /// Enables transmit interrupts for the events that `wait` checks
     ///
     /// Overwrites any interrupt flags that were previously set.
     pub fn enable_tx_interrupts(&mut self)
@@ -502,6 +1344,84 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
         Ok(())
     }
 
+    /// Configures the polarity of the IRQ output
+    ///
+    /// If `active_high` is `true`, the IRQ line is driven high while an
+    /// interrupt is pending. Otherwise it is driven low. The DW1000 defaults to
+    /// active high. See user manual, section 7.2.6.
+    pub fn set_interrupt_polarity(&mut self, active_high: bool)
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.ll.sys_cfg().modify(|_, w| w.hirq_pol(active_high as u8))?;
+        Ok(())
+    }
+
+    /// Configures the PHY header mode
+    ///
+    /// In [`PhrMode::Extended`], frames of up to 1023 bytes can be received,
+    /// and their length is decoded from both RXFLEN and RXFLE. This is a
+    /// proprietary Decawave mode, and both peers must use the same mode, or
+    /// frames won't be decoded. [`DW1000::send`] still only supports frames
+    /// of standard length. Use [`DW1000::send_raw`] to send longer frames.
+    /// See user manual, section 7.2.6.
+    pub fn set_phr_mode(&mut self, mode: PhrMode)
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.ll.sys_cfg().modify(|_, w| w.phr_mode(mode as u8))?;
+        Ok(())
+    }
+
+    /// Switches the radio to a different channel
+    ///
+    /// Writes the channel number to CHAN_CTRL and applies the recommended
+    /// RF and PLL tuning for `channel`. The PLLs need some time to settle
+    /// afterwards, so this clears CPLOCK and the losing lock flags, and waits
+    /// until CPLOCK is set again (see [`DW1000::wait_for_pll_lock`]). The
+    /// timer must already be started. Returns [`Error::Timeout`], if it
+    /// expires before the PLLs locked.
+    ///
+    /// Transmissions and receptions apply the channel of their
+    /// [`TxConfig`]/[`RxConfig`] again, which should match `channel`. See user
+    /// manual, sections 7.2.17, 7.2.32, and 7.2.41 to 7.2.44.
+    pub fn set_channel<T>(&mut self, channel: UwbChannel, timer: &mut T)
+        -> Result<(), Error<SPI, CS>>
+        where
+            T: CountDown,
+    {
+        self.ll.chan_ctrl().modify(|_, w|
+            w
+                .tx_chan(channel as u8)
+                .rx_chan(channel as u8)
+        )?;
+
+        self.ll.rf_rxctrlh().write(|w| w.value(channel.get_recommended_rf_rxctrlh()))?;
+        self.ll.rf_txctrl().write(|w| w.value(channel.get_recommended_rf_txctrl()))?;
+        self.ll.tc_pgdelay().write(|w| w.value(channel.get_recommended_tc_pgdelay()))?;
+        self.ll.fs_pllcfg().write(|w| w.value(channel.get_recommended_fs_pllcfg()))?;
+        self.ll.fs_plltune().write(|w| w.value(channel.get_recommended_fs_plltune()))?;
+
+        self.ll.sys_status().write(|w|
+            w
+                .cplock(0b1)
+                .clkpll_ll(0b1)
+                .rfpll_ll(0b1)
+        )?;
+        self.wait_for_pll_lock(timer)
+    }
+
+    /// Configures on which SPI clock edge MISO data is launched
+    ///
+    /// If `opposite_edge` is `false`, the DW1000 launches data on the sampling
+    /// edge, which is the default. If it is `true`, data is launched on the
+    /// opposite edge, which may give better timing at high SPI clock rates.
+    /// See user manual, section 7.2.6.
+    pub fn set_spi_edge(&mut self, opposite_edge: bool)
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.ll.sys_cfg().modify(|_, w| w.spi_edge(opposite_edge as u8))?;
+        Ok(())
+    }
+
     /// Configures the gpio pins to operate as LED output.
     ///
     /// - Note: This means that the function of the gpio pins change
@@ -546,6 +1466,322 @@ impl<SPI, CS> DW1000<SPI, CS, Ready>
 
         Ok(())
     }
+
+    /// Routes an event to its GPIO pin
+    ///
+    /// Switches `pin` to the alternate function that outputs `event`. This can
+    /// be used to drive an LED, or as an additional, faster interrupt line.
+    /// Each event can only be routed to one specific pin, according to the
+    /// GPIO mode table (user manual, section 7.2.39.1):
+    ///
+    /// - [`GpioEvent::RxOk`] on GPIO0
+    /// - [`GpioEvent::Sfd`] on GPIO1
+    /// - [`GpioEvent::Rx`] on GPIO2
+    /// - [`GpioEvent::Tx`] on GPIO3
+    /// - [`GpioEvent::ExtPa`] on GPIO4
+    /// - [`GpioEvent::ExtTxEnable`] on GPIO5
+    /// - [`GpioEvent::ExtRxEnable`] on GPIO6
+    ///
+    /// Returns [`Error::InvalidConfiguration`], if `event` can't be routed to
+    /// `pin`. Please note that the LED outputs require the debounce and
+    /// kilohertz clocks, which are enabled by [`DW1000::configure_leds`].
+    pub fn route_event_to_gpio(&mut self, event: GpioEvent, pin: GpioPin)
+        -> Result<(), Error<SPI, CS>>
+    {
+        if event.pin() != pin {
+            return Err(Error::InvalidConfiguration);
+        }
+
+        self.ll.gpio_mode().modify(|_, w|
+            match pin {
+                GpioPin::Gpio0 => w.msgp0(0b01),
+                GpioPin::Gpio1 => w.msgp1(0b01),
+                GpioPin::Gpio2 => w.msgp2(0b01),
+                GpioPin::Gpio3 => w.msgp3(0b01),
+                GpioPin::Gpio4 => w.msgp4(0b01),
+                GpioPin::Gpio5 => w.msgp5(0b01),
+                GpioPin::Gpio6 => w.msgp6(0b01),
+                GpioPin::Gpio7 => w.msgp7(0b01),
+                GpioPin::Gpio8 => w.msgp8(0b01),
+            }
+        )?;
+
+        Ok(())
+    }
+
+    /// Sets the transmit power, in steps of 0.5 dB
+    ///
+    /// Each power setting in TX_POWER consists of a coarse gain (0 to 15 dB,
+    /// in steps of 2.5 dB) and a fine gain (0 to 15.5 dB, in steps of 0.5 dB).
+    /// This method maps `index` to a combination of the two, with `0` being
+    /// the lowest gain, and `61` the highest (30.5 dB). The same setting is
+    /// used for all fields in TX_POWER, so this works regardless of whether
+    /// smart TX power control is enabled.
+    ///
+    /// This is intended as a primitive for adaptive power control. Please
+    /// make sure to stay within the limits of your local regulations. Returns
+    /// [`Error::InvalidConfiguration`], if `index` is larger than `61`. See
+    /// user manual, section 7.2.31.
+    pub fn set_tx_power_index(&mut self, index: u8)
+        -> Result<(), Error<SPI, CS>>
+    {
+        const COARSE_STEPS: u8 = 6; // 0 dB to 15 dB, in steps of 2.5 dB
+        const FINE_PER_COARSE: u8 = 5; // one coarse step equals 5 fine steps
+        const FINE_MAX: u8 = 31;
+
+        if index > COARSE_STEPS * FINE_PER_COARSE + FINE_MAX {
+            return Err(Error::InvalidConfiguration);
+        }
+
+        let coarse = (index / FINE_PER_COARSE).min(COARSE_STEPS);
+        let fine   = index - coarse * FINE_PER_COARSE;
+
+        // The coarse gain is encoded inversely: 0b110 is 0 dB, 0b000 is 15 dB.
+        let setting = (COARSE_STEPS - coarse) << 5 | fine;
+
+        self.ll.tx_power().write(|w|
+            w
+                .boostnorm(setting)
+                .boostp500(setting)
+                .boostp250(setting)
+                .boostp125(setting)
+        )?;
+
+        // The setting applies regardless of smart TX power control, so there's
+        // nothing to restore when it is enabled again.
+        self.smart_tx_power = None;
+
+        Ok(())
+    }
+
+    /// Configures the GPIOs to control an external PA and LNA
+    ///
+    /// Switches GPIO4, GPIO5, and GPIO6 to their EXTPA, EXTTXE, and EXTRXE
+    /// functions, respectively. EXTTXE and EXTPA are asserted while the
+    /// transmitter is active, EXTRXE while the receiver is active, which means
+    /// the external components are enabled before the preamble is sent or the
+    /// receiver starts listening, and disabled afterwards.
+    ///
+    /// Fine grain TX sequencing is disabled, as it would interfere with the
+    /// external power amplifier. See user manual, sections 7.2.39.1 and
+    /// 7.2.50.4.
+    pub fn configure_external_pa_lna(&mut self) -> Result<(), Error<SPI, CS>> {
+        self.ll.gpio_mode().modify(|_, w|
+            w
+                .msgp4(0b01) // EXTPA
+                .msgp5(0b01) // EXTTXE
+                .msgp6(0b01) // EXTRXE
+        )?;
+        self.ll.pmsc_txfseq().write(|w| w.txfineseq(0x0000))?;
+
+        Ok(())
+    }
+
+    /// Configures snooze mode
+    ///
+    /// In snooze mode, the DW1000 uses the snooze timer to time its low-power
+    /// phases, waking up automatically once `period` has elapsed. If `repeat`
+    /// is `true`, the DW1000 will go back to snooze after waking up, instead of
+    /// staying awake.
+    ///
+    /// This differs from deep sleep, which can only be left via the WAKEUP pin
+    /// or SPI chip select, and which doesn't keep the low-power oscillator
+    /// running. Because of that, snooze draws somewhat more current than deep
+    /// sleep (roughly 1 µA vs 50 nA, according to the datasheet), but doesn't
+    /// require any action from the host to wake up. The sleep counter in the
+    /// AON block, which is used for timed sleep, is not affected.
+    ///
+    /// The snooze timer (SNOZ_TIM in PMSC_SNOZT) counts cycles of the
+    /// low-power oscillator, which is not very precise. This method
+    /// calibrates the oscillator first (see
+    /// [`DW1000::calibrate_sleep_clock`]), to convert `period` into
+    /// oscillator cycles. Returns [`Error::InvalidConfiguration`], if `period`
+    /// is shorter than one cycle, or longer than the 255 cycles the snooze
+    /// timer can count. See user manual, sections 7.2.50.2 and 7.2.50.3.
+    pub fn configure_snooze(&mut self, period: Duration, repeat: bool)
+        -> Result<(), Error<SPI, CS>>
+    {
+        // One cycle of XTAL/2 (19.2 MHz) lasts 3328 DW1000 time units.
+        const UNITS_PER_XTAL_CYCLE: u64 = 3328;
+
+        let xtal_cycles_per_lp_cycle = self.calibrate_sleep_clock()? as u64;
+        if xtal_cycles_per_lp_cycle == 0 {
+            return Err(Error::InvalidConfiguration);
+        }
+
+        let lp_cycles =
+            period.value() / (UNITS_PER_XTAL_CYCLE * xtal_cycles_per_lp_cycle);
+        if lp_cycles == 0 || lp_cycles > u8::MAX as u64 {
+            return Err(Error::InvalidConfiguration);
+        }
+
+        self.ll.pmsc_snozt().write(|w| w.snoz_tim(lp_cycles as u8))?;
+        self.ll.pmsc_ctrl1().modify(|_, w|
+            w
+                .snoze(0b1)
+                .snozr(repeat as u8)
+        )?;
+
+        Ok(())
+    }
+
+    /// Calibrate the low-power oscillator
+    ///
+    /// Measures the period of the low-power oscillator that drives the sleep
+    /// counter, and returns it as the number of XTAL/2 (19.2 MHz) cycles per
+    /// oscillator cycle. The oscillator frequency in Hz is `19_200_000 /
+    /// result`. Since the oscillator is rather imprecise, this can be used to
+    /// compute sleep times more accurately than assuming its nominal frequency.
+    ///
+    /// See user manual, sections 7.2.44 and 7.2.47.
+    pub fn calibrate_sleep_clock(&mut self) -> Result<u32, Error<SPI, CS>> {
+        // Addresses of the calibration result in the AON memory
+        const LPOSC_CAL_0: u8 = 117;
+        const LPOSC_CAL_1: u8 = 118;
+
+        // Run the calibration. The other fields in AON_CFG1 configure the
+        // sleep counter, so they must be left alone.
+        self.ll.aon_cfg1().modify(|_, w| w.lposc_c(0b1))?;
+        self.ll.aon_ctrl().write(|w| w.upl_cfg(0b1))?;
+        self.ll.aon_ctrl().write(|w| w.upl_cfg(0b0))?;
+        self.ll.aon_cfg1().modify(|_, w| w.lposc_c(0b0))?;
+        self.ll.aon_ctrl().write(|w| w.upl_cfg(0b1))?;
+        self.ll.aon_ctrl().write(|w| w.upl_cfg(0b0))?;
+
+        // Reading the result from AON memory requires the system clock to run
+        // from the crystal.
+        self.ll.pmsc_ctrl0().modify(|_, w| w.sysclks(0b01))?;
+
+        let high = self.read_aon(LPOSC_CAL_1)?;
+        let low  = self.read_aon(LPOSC_CAL_0)?;
+
+        self.ll.aon_ctrl().write(|w| w.dca_enab(0b0))?;
+        self.ll.pmsc_ctrl0().modify(|_, w| w.sysclks(0b00))?;
+
+        Ok((high as u32) << 8 | low as u32)
+    }
+
+    /// Read a byte from the AON memory, using direct access
+    fn read_aon(&mut self, address: u8) -> Result<u8, Error<SPI, CS>> {
+        self.ll.aon_addr().write(|w| w.value(address))?;
+        self.ll.aon_ctrl().write(|w| w.dca_enab(0b1))?;
+        self.ll.aon_ctrl().write(|w| w.dca_enab(0b1).dca_read(0b1))?;
+        Ok(self.ll.aon_rdat().read()?.value())
+    }
+
+    /// Puts the DW1000 into deep sleep
+    ///
+    /// `config` determines what the DW1000 does when it wakes up. The DW1000
+    /// wakes up, if its WAKEUP pin is driven high, or if the SPI chip select is
+    /// held low for at least 500 µs. Both are outside of the scope of this
+    /// driver. Once the DW1000 has woken up, call [`DW1000::finish_waking`] to
+    /// get back to the `Ready` state.
+    ///
+    /// See user manual, section 7.2.44 to 7.2.49.
+    pub fn enter_sleep(mut self, config: AonConfig)
+        -> Result<DW1000<SPI, CS, Sleeping>, Error<SPI, CS>>
+    {
+        self.ll.aon_wcfg().write(|w|
+            w
+                .onw_radc(config.run_adc as u8)
+                .onw_rx(config.enable_receiver as u8)
+                .onw_leui(config.load_eui as u8)
+                .onw_ldc(config.restore_config as u8)
+                .onw_l64p(config.load_length64 as u8)
+                .pres_sleep(config.preserve_sleep as u8)
+                .onw_llde(config.load_lde as u8)
+                .onw_lldo(config.load_ldotune as u8)
+        )?;
+        self.ll.aon_cfg0().modify(|_, w|
+            w
+                .sleep_en(0b1)
+                .wake_pin(0b1)
+                .wake_spi(0b1)
+        )?;
+
+        // Copy the configuration to the AON memory. Since sleep is enabled,
+        // this also puts the DW1000 to sleep.
+        self.ll.aon_ctrl().write(|w| w.save(0b0))?;
+        self.ll.aon_ctrl().write(|w| w.save(0b1))?;
+
+        Ok(DW1000 {
+            ll:    self.ll,
+            seq:   self.seq,
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
+            state: Sleeping,
+        })
+    }
+
+    /// Performs a soft reset of the DW1000
+    ///
+    /// Resets all registers to their default values and returns the driver in
+    /// the `Uninitialized` state, from which it needs to be initialized again.
+    /// Use [`DW1000::read_config`] before the reset and
+    /// [`DW1000::restore_config`] afterwards, to keep the node configuration.
+    /// Per-channel antenna delays set with
+    /// [`DW1000::set_antenna_delay_for_channel`] are kept by the driver.
+    ///
+    /// After the reset, the DW1000 runs from its crystal oscillator until it is
+    /// initialized, so the SPI clock must not exceed 3 MHz until then. See user
+    /// manual, section 7.2.50.1.
+    pub fn reset(mut self)
+        -> Result<DW1000<SPI, CS, Uninitialized>, Error<SPI, CS>>
+    {
+        self.ll.pmsc_ctrl0().modify(|_, w| w.sysclks(0b01))?;
+        self.ll.pmsc_ctrl0().modify(|_, w| w.softreset(0b0000))?;
+        self.ll.pmsc_ctrl0().modify(|_, w| w.softreset(0b1111))?;
+
+        Ok(DW1000 {
+            ll:    self.ll,
+            seq:   self.seq,
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
+            state: Uninitialized,
+        })
+    }
+}
+
+impl<SPI, CS> DW1000<SPI, CS, Sleeping>
+    where
+        SPI: spi::Transfer<u8> + spi::Write<u8>,
+        CS:  OutputPin,
+{
+    /// Finishes waking up and returns to the `Ready` state
+    ///
+    /// Call this after waking up the DW1000, as described in
+    /// [`DW1000::enter_sleep`]. The crystal oscillator needs some time to
+    /// stabilize after waking up. If the DW1000 doesn't respond yet, this
+    /// method returns [`Error::NoResponse`], and can be tried again.
+    ///
+    /// If the DW1000 was not configured to restore its configuration on
+    /// wake-up (see [`AonConfig::restore_config`]), it needs to be initialized
+    /// again. In that case, call [`DW1000::reset`] after this method, then
+    /// [`DW1000::init`].
+    pub fn finish_waking(mut self)
+        -> Result<DW1000<SPI, CS, Ready>, ErrorWithDriver<SPI, CS, Sleeping>>
+    {
+        // Can't use `map_err` and `?` here, as the compiler will complain
+        // about `self` moving into the closure.
+        match self.check_device_id() {
+            Ok(())     => (),
+            Err(error) => return Err((self, error)),
+        }
+
+        Ok(DW1000 {
+            ll:    self.ll,
+            seq:   self.seq,
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
+            state: Ready,
+        })
+    }
 }
 
 impl<SPI, CS> DW1000<SPI, CS, Sending>
@@ -596,8 +1832,11 @@ impl<SPI, CS> DW1000<SPI, CS, Sending>
         // ATTENTION:
         // If you're changing anything about which SYS_STATUS flags are being
         // checked in this method, also make sure to update `enable_interrupts`.
+        //
+        // Only the low bytes of SYS_STATUS are needed here, so we don't read
+        // the whole register, to save some time while polling.
         let sys_status = self.ll
-            .sys_status()
+            .sys_status_lo()
             .read()
             .map_err(|error| nb::Error::Other(Error::Spi(error)))?;
 
@@ -607,20 +1846,46 @@ impl<SPI, CS> DW1000<SPI, CS, Sending>
             return Err(nb::Error::WouldBlock);
         }
 
-        // Frame sent
-        self.reset_flags()
-            .map_err(|error| nb::Error::Other(error))?;
+        // Frame sent. Read the time stamp before clearing the status bits, so
+        // it is captured together with the event that made it valid.
+        let tx_time = self.ll
+            .tx_time()
+            .read()
+            .map_err(|error| nb::Error::Other(Error::Spi(error)))?
+            .tx_stamp();
+        // Since hardware timestamps fit within 40 bits, the following should
+        // never panic.
+        self.state.tx_time = Some(Instant::new(tx_time).unwrap());
+
+        self.clear_tx_status()
+            .map_err(nb::Error::Other)?;
         self.state.finished = true;
 
         Ok(())
     }
 
+    /// Returns the time the frame was sent
+    ///
+    /// This is the fully adjusted time stamp from TX_TIME, which includes the
+    /// TX antenna delay. [`DW1000::wait`] reads it as soon as it sees that the
+    /// frame was sent, before it clears the status bits, so this returns the
+    /// time stamp once `wait` has returned successfully, and `None` before
+    /// that.
+    pub fn tx_time(&self) -> Option<Instant> {
+        self.state.tx_time
+    }
+
+    /// Indicates whether `wait` has reported that the frame was sent
+    pub(crate) fn is_finished(&self) -> bool {
+        self.state.finished
+    }
+
     /// Finishes sending and returns to the `Ready` state
     ///
     /// If the send operation has finished, as indicated by `wait`, this is a
     /// no-op. If the send operation is still ongoing, it will be aborted.
     pub fn finish_sending(mut self)
-        -> Result<DW1000<SPI, CS, Ready>, (Self, Error<SPI, CS>)>
+        -> Result<DW1000<SPI, CS, Ready>, ErrorWithDriver<SPI, CS, Sending>>
     {
         if !self.state.finished {
             // Can't use `map_err` and `?` here, as the compiler will complain
@@ -629,7 +1894,7 @@ impl<SPI, CS> DW1000<SPI, CS, Sending>
                 Ok(())     => (),
                 Err(error) => return Err((self, error)),
             }
-            match self.reset_flags() {
+            match self.clear_tx_status() {
                 Ok(())     => (),
                 Err(error) => return Err((self, error)),
             }
@@ -638,22 +1903,25 @@ impl<SPI, CS> DW1000<SPI, CS, Sending>
         Ok(DW1000 {
             ll:    self.ll,
             seq:   self.seq,
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
             state: Ready,
         })
     }
 
-    fn reset_flags(&mut self) -> Result<(), Error<SPI, CS>> {
-        self.ll
-            .sys_status()
-            .write(|w|
-                w
-                    .txfrb(0b1) // Transmit Frame Begins
-                    .txprs(0b1) // Transmit Preamble Sent
-                    .txphs(0b1) // Transmit PHY Header Sent
-                    .txfrs(0b1) // Transmit Frame Sent
-            )?;
-
-        Ok(())
+    /// Cancels sending and returns to the `Ready` state
+    ///
+    /// Unlike [`DW1000::finish_sending`], this always forces the DW1000 into
+    /// IDLE mode and clears the TX status flags, regardless of whether the
+    /// send operation has finished. This is useful, if the operation is
+    /// abandoned, for example because a time slot ended.
+    pub fn cancel(mut self)
+        -> Result<DW1000<SPI, CS, Ready>, ErrorWithDriver<SPI, CS, Sending>>
+    {
+        self.state.finished = false;
+        self.finish_sending()
     }
 }
 
@@ -674,8 +1942,100 @@ impl<SPI, CS> DW1000<SPI, CS, Receiving>
     /// driver, but please note that if you're using the DWM1001 module or
     /// DWM1001-Dev board, that the `dwm1001` crate has explicit support for
     /// this.
+    ///
+    /// If [`RxConfig::keep_corrupted_frames`] is enabled, a frame that failed
+    /// to be received due to an FCS or PHY header error is copied into
+    /// `buffer`, and [`Error::CorruptedFcs`] or [`Error::CorruptedPhy`] is
+    /// returned.
     pub fn wait<'b>(&mut self, buffer: &'b mut [u8])
         -> nb::Result<Message<'b>, Error<SPI, CS>>
+    {
+        let (rx_time, rx_time_raw) = match self.wait_for_frame() {
+            Ok(rx_times) =>
+                rx_times,
+            Err(nb::Error::Other(error)) =>
+                return Err(nb::Error::Other(
+                    self.keep_corrupted_frame(error, buffer)
+                )),
+            Err(nb::Error::WouldBlock) =>
+                return Err(nb::Error::WouldBlock),
+        };
+        self.read_frame(rx_time, rx_time_raw, buffer)
+            .map_err(nb::Error::Other)
+    }
+
+    /// Wait for a frame addressed to one of several addresses
+    ///
+    /// Works like [`DW1000::wait`], but additionally checks the destination of
+    /// the received frame in software. Frames sent to one of the `accepted`
+    /// addresses, or to a broadcast address (see [`frame::is_broadcast`]), are
+    /// returned. Any other frame, including frames without a destination, is
+    /// rejected with [`Error::NotForUs`].
+    ///
+    /// The hardware frame filter only supports a single address, so it must be
+    /// disabled for this to be useful (see [`RxConfig::frame_filtering`]).
+    pub fn wait_filtered<'b>(&mut self,
+        buffer:   &'b mut [u8],
+        accepted: &[mac::Address],
+    )
+        -> nb::Result<Message<'b>, Error<SPI, CS>>
+    {
+        let message = self.wait(buffer)?;
+
+        let for_us = match &message.frame.header.destination {
+            Some(destination) =>
+                frame::is_broadcast(destination)
+                    || accepted.contains(destination),
+            None =>
+                false,
+        };
+        if !for_us {
+            return Err(nb::Error::Other(Error::NotForUs));
+        }
+
+        Ok(message)
+    }
+
+    /// Wait for receive operation to finish, with a software timeout
+    ///
+    /// Busily waits using [`DW1000::wait`], until either a frame is received,
+    /// an error occurs, or `timer` expires. The hardware frame wait timeout is
+    /// limited to roughly 65 ms, so this can be used for longer timeouts. The
+    /// timer must already be started.
+    ///
+    /// Returns [`Error::Timeout`], if the timer expired. In that case, the
+    /// receiver is disabled, so the radio returns to a clean state.
+    pub fn wait_timeout<'b, T>(&mut self, buffer: &'b mut [u8], timer: &mut T)
+        -> Result<Message<'b>, Error<SPI, CS>>
+        where
+            T: CountDown,
+    {
+        let (rx_time, rx_time_raw) = loop {
+            match self.wait_for_frame() {
+                Ok(rx_times) =>
+                    break rx_times,
+                Err(nb::Error::Other(error)) =>
+                    return Err(self.keep_corrupted_frame(error, buffer)),
+                Err(nb::Error::WouldBlock) =>
+                    (),
+            }
+
+            if timer.wait().is_ok() {
+                self.force_idle()?;
+                self.state.finished = true;
+                return Err(Error::Timeout);
+            }
+        };
+
+        self.read_frame(rx_time, rx_time_raw, buffer)
+    }
+
+    /// Checks whether a frame was received
+    ///
+    /// Returns the adjusted and raw RX time stamps, if a frame is ready to be
+    /// read.
+    fn wait_for_frame(&mut self)
+        -> nb::Result<(Instant, Instant), Error<SPI, CS>>
     {
         // ATTENTION:
         // If you're changing anything about which SYS_STATUS flags are being
@@ -701,6 +2061,11 @@ impl<SPI, CS> DW1000<SPI, CS, Receiving>
                 return Err(nb::Error::Other(Error::FrameWaitTimeout));
             }
             if sys_status.rxovrr() == 0b1 {
+                // The user manual (section 4.3) requires the receiver to be
+                // reset after an overrun. Clearing the status is not enough,
+                // as the receiver might be left in a bad state otherwise.
+                self.recover_from_overrun()
+                    .map_err(nb::Error::Other)?;
                 return Err(nb::Error::Other(Error::Overrun));
             }
             if sys_status.rxpto() == 0b1 {
@@ -727,81 +2092,204 @@ impl<SPI, CS> DW1000<SPI, CS, Receiving>
 
         // Wait until LDE processing is done. Before this is finished, the RX
         // time stamp is not available.
-        if sys_status.ldedone() == 0b0 {
+        if self.state.lde_enabled && sys_status.ldedone() == 0b0 {
             return Err(nb::Error::WouldBlock);
         }
-        let rx_time = self.ll()
+        let rx_time_reg = self.ll()
             .rx_time()
             .read()
-            .map_err(|error| nb::Error::Other(Error::Spi(error)))?
-            .rx_stamp();
+            .map_err(|error| nb::Error::Other(Error::Spi(error)))?;
 
-        // `rx_time` comes directly from the register, which should always
-        // contain a 40-bit timestamp. Unless the hardware or its documentation
+        // The time stamps come directly from the register, which should always
+        // contain 40-bit timestamps. Unless the hardware or its documentation
         // are buggy, the following should never panic.
-        let rx_time = Instant::new(rx_time).unwrap();
+        let rx_time     = Instant::new(rx_time_reg.rx_stamp()).unwrap();
+        let rx_time_raw = Instant::new(rx_time_reg.rx_rawst()).unwrap();
 
         // Reset status bits. This is not strictly necessary, but it helps, if
         // you have to inspect SYS_STATUS manually during debugging.
-        self.ll()
-            .sys_status()
-            .write(|w|
-                w
-                    .rxprd(0b1)   // Receiver Preamble Detected
-                    .rxsfdd(0b1)  // Receiver SFD Detected
-                    .ldedone(0b1) // LDE Processing Done
-                    .rxphd(0b1)   // Receiver PHY Header Detected
-                    .rxphe(0b1)   // Receiver PHY Header Error
-                    .rxdfr(0b1)   // Receiver Data Frame Ready
-                    .rxfcg(0b1)   // Receiver FCS Good
-                    .rxfce(0b1)   // Receiver FCS Error
-                    .rxrfsl(0b1)  // Receiver Reed Solomon Frame Sync Loss
-                    .rxrfto(0b1)  // Receiver Frame Wait Timeout
-                    .ldeerr(0b1)  // Leading Edge Detection Processing Error
-                    .rxovrr(0b1)  // Receiver Overrun
-                    .rxpto(0b1)   // Preamble Detection Timeout
-                    .rxsfdto(0b1) // Receiver SFD Timeout
-                    .rxrscs(0b1)  // Receiver Reed-Solomon Correction Status
-                    .rxprej(0b1)  // Receiver Preamble Rejection
-            )
-            .map_err(|error| nb::Error::Other(Error::Spi(error)))?;
+        self.reset_flags()
+            .map_err(nb::Error::Other)?;
 
-        // Read received frame
-        let rx_finfo = self.ll()
-            .rx_finfo()
-            .read()
-            .map_err(|error| nb::Error::Other(Error::Spi(error)))?;
-        let rx_buffer = self.ll()
-            .rx_buffer()
-            .read()
-            .map_err(|error| nb::Error::Other(Error::Spi(error)))?;
+        Ok((rx_time, rx_time_raw))
+    }
+
+    /// Wait for receive operation to finish, without a user-provided buffer
+    ///
+    /// Works like [`DW1000::wait`], except that the frame is stored in a
+    /// buffer owned by this instance, and the returned [`Message`] borrows
+    /// from it. This saves you from having to provide a buffer.
+    ///
+    /// The returned message borrows this instance mutably, so it needs to be
+    /// dropped before any other method can be called. The next receive
+    /// operation overwrites the buffer, invalidating the previous message.
+    ///
+    /// The internal buffer only fits frames of standard length. Longer frames,
+    /// as received in extended PHR mode, result in [`Error::BufferTooSmall`].
+    pub fn wait_borrowed(&mut self)
+        -> nb::Result<Message<'_>, Error<SPI, CS>>
+    {
+        let (rx_time, rx_time_raw) = self.wait_for_frame()?;
+        let (len, rx_finfo, rx_buffer) = self.read_rx_buffer()
+            .map_err(nb::Error::Other)?;
 
-        let len = rx_finfo.rxflen() as usize;
+        self.state.buffer.copy_from_slice(rx_buffer.data());
+
+        let (frame, security) = frame::decode(&self.state.buffer[..len])
+            .map_err(|error| nb::Error::Other(Error::Frame(error)))?;
+
+        Ok(Message {
+            rx_time,
+            rx_time_raw,
+            rx_bit_rate:       BitRate::from_bits(rx_finfo.rxbr()),
+            was_ranging_frame: rx_finfo.rng() == 0b1,
+            frame,
+            security,
+            bytes: without_fcs(&self.state.buffer[..len]),
+        })
+    }
+
+    /// Reads the received frame into the buffer
+    fn read_frame<'b>(&mut self,
+        rx_time:     Instant,
+        rx_time_raw: Instant,
+        buffer:      &'b mut [u8],
+    )
+        -> Result<Message<'b>, Error<SPI, CS>>
+    {
+        let (len, rx_finfo) = self.read_frame_info()?;
 
         if buffer.len() < len {
-            return Err(nb::Error::Other(
-                Error::BufferTooSmall { required_len: len }
-            ))
+            return Err(Error::BufferTooSmall { required_len: len })
         }
 
-        buffer[..len].copy_from_slice(&rx_buffer.data()[..len]);
+        self.ll.read_rx_buffer_at(0, &mut buffer[..len])?;
 
-        let frame = buffer[..len].read_with(&mut 0, FooterMode::None)
-            .map_err(|error| nb::Error::Other(Error::Frame(error)))?;
+        // The message borrows the buffer twice, for the frame and the raw
+        // bytes, so we need a shared reference from here on.
+        let buffer: &'b [u8] = buffer;
+
+        let (frame, security) = frame::decode(&buffer[..len])
+            .map_err(|error| Error::Frame(error))?;
 
         Ok(Message {
             rx_time,
+            rx_time_raw,
+            rx_bit_rate:       BitRate::from_bits(rx_finfo.rxbr()),
+            was_ranging_frame: rx_finfo.rng() == 0b1,
             frame,
+            security,
+            bytes: without_fcs(&buffer[..len]),
         })
     }
 
+    /// Copies a corrupted frame into the buffer, if configured to do so
+    ///
+    /// Returns the error that should be passed on to the caller.
+    fn keep_corrupted_frame(&mut self,
+        error:  Error<SPI, CS>,
+        buffer: &mut [u8],
+    )
+        -> Error<SPI, CS>
+    {
+        if !self.state.keep_corrupted_frames {
+            return error;
+        }
+
+        match error {
+            Error::Fcs => match self.read_corrupted_frame(buffer) {
+                Ok(len)    => Error::CorruptedFcs { len },
+                Err(error) => error,
+            },
+            Error::Phy => match self.read_corrupted_frame(buffer) {
+                Ok(len)    => Error::CorruptedPhy { len },
+                Err(error) => error,
+            },
+            error => error,
+        }
+    }
+
+    /// Reads a corrupted frame into the buffer, returning its length
+    ///
+    /// The minimum frame length isn't checked, and the frame is truncated to
+    /// the length of the buffer.
+    fn read_corrupted_frame(&mut self, buffer: &mut [u8])
+        -> Result<usize, Error<SPI, CS>>
+    {
+        let rx_finfo = self.ll()
+            .rx_finfo()
+            .read()?;
+
+        let len = frame_len(&rx_finfo).min(buffer.len());
+        self.ll.read_rx_buffer_at(0, &mut buffer[..len])?;
+
+        Ok(len)
+    }
+
+    /// Reads the received frame's length, RX_FINFO, and the receive buffer
+    ///
+    /// Only supports frames of standard length.
+    fn read_rx_buffer(&mut self)
+        -> Result<(usize, ll::rx_finfo::R, ll::rx_buffer::R), Error<SPI, CS>>
+    {
+        let (len, rx_finfo) = self.read_frame_info()?;
+        if len > self.state.buffer.len() {
+            return Err(Error::BufferTooSmall { required_len: len });
+        }
+
+        let rx_buffer = self.ll()
+            .rx_buffer()
+            .read()?;
+
+        Ok((len, rx_finfo, rx_buffer))
+    }
+
+    /// Resets the receiver after an overrun
+    ///
+    /// Forces the DW1000 into IDLE mode, resets the receiver, and clears the
+    /// RX status flags, including RXOVRR.
+    fn recover_from_overrun(&mut self) -> Result<(), Error<SPI, CS>> {
+        self.force_idle()?;
+        self.reset_receiver()?;
+        self.reset_flags()?;
+        self.state.finished = true;
+
+        Ok(())
+    }
+
+    /// Reads the received frame's length and RX_FINFO
+    fn read_frame_info(&mut self)
+        -> Result<(usize, ll::rx_finfo::R), Error<SPI, CS>>
+    {
+        let rx_finfo = self.ll()
+            .rx_finfo()
+            .read()?;
+
+        let len = frame_len(&rx_finfo);
+        if len < self.state.min_frame_len {
+            return Err(Error::FrameTooShort);
+        }
+
+        Ok((len, rx_finfo))
+    }
+
     /// Finishes receiving and returns to the `Ready` state
     ///
     /// If the receive operation has finished, as indicated by `wait`, this is a
     /// no-op. If the receive operation is still ongoing, it will be aborted.
+    // The receive buffer makes the error large, but without an allocator, it
+    // can't be boxed.
+    #[allow(clippy::result_large_err)]
     pub fn finish_receiving(mut self)
-        -> Result<DW1000<SPI, CS, Ready>, (Self, Error<SPI, CS>)>
+        -> Result<DW1000<SPI, CS, Ready>, ErrorWithDriver<SPI, CS, Receiving>>
     {
+        // Can't use `map_err` and `?` here, as the compiler will complain
+        // about `self` moving into the closure.
+        match self.check_stuck() {
+            Ok(())     => (),
+            Err(error) => return Err((self, error)),
+        }
+
         if !self.state.finished {
             // Can't use `map_err` and `?` here, as the compiler will complain
             // about `self` moving into the closure.
@@ -814,47 +2302,868 @@ impl<SPI, CS> DW1000<SPI, CS, Receiving>
         Ok(DW1000 {
             ll:    self.ll,
             seq:   self.seq,
+
+            antenna_delays: self.antenna_delays,
+            rx_needs_reset: self.rx_needs_reset,
+            smart_tx_power: self.smart_tx_power,
             state: Ready,
         })
     }
+
+    /// Cancels receiving and returns to the `Ready` state
+    ///
+    /// Unlike [`DW1000::finish_receiving`], this always forces the DW1000 into
+    /// IDLE mode and clears the RX status flags, regardless of whether the
+    /// receive operation has finished. This is useful, if the operation is
+    /// abandoned, for example because a time slot ended.
+    // The receive buffer makes the error large, but without an allocator, it
+    // can't be boxed.
+    #[allow(clippy::result_large_err)]
+    pub fn cancel(mut self)
+        -> Result<DW1000<SPI, CS, Ready>, ErrorWithDriver<SPI, CS, Receiving>>
+    {
+        // Can't use `map_err` and `?` here, as the compiler will complain
+        // about `self` moving into the closure.
+        match self.check_stuck() {
+            Ok(())     => (),
+            Err(error) => return Err((self, error)),
+        }
+        match self.force_idle() {
+            Ok(())     => (),
+            Err(error) => return Err((self, error)),
+        }
+        match self.reset_flags() {
+            Ok(())     => (),
+            Err(error) => return Err((self, error)),
+        }
+
+        self.state.finished = true;
+        self.finish_receiving()
+    }
+
+    /// Checks whether the receiver needs to be reset before the next receive
+    ///
+    /// Must be called before the DW1000 is forced into IDLE mode, as that
+    /// hides a stuck receiver. If the receive operation hasn't finished, the
+    /// receiver is still active, and there's no way to tell whether it is
+    /// stuck. It is reset before the next receive in that case too.
+    fn check_stuck(&mut self) -> Result<(), Error<SPI, CS>> {
+        if self.transceiver_active()? {
+            self.rx_needs_reset = true;
+        }
+
+        Ok(())
+    }
+
+    fn reset_flags(&mut self) -> Result<(), Error<SPI, CS>> {
+        self.ll
+            .sys_status()
+            .write(|w|
+                w
+                    .rxprd(0b1)   // Receiver Preamble Detected
+                    .rxsfdd(0b1)  // Receiver SFD Detected
+                    .ldedone(0b1) // LDE Processing Done
+                    .rxphd(0b1)   // Receiver PHY Header Detected
+                    .rxphe(0b1)   // Receiver PHY Header Error
+                    .rxdfr(0b1)   // Receiver Data Frame Ready
+                    .rxfcg(0b1)   // Receiver FCS Good
+                    .rxfce(0b1)   // Receiver FCS Error
+                    .rxrfsl(0b1)  // Receiver Reed Solomon Frame Sync Loss
+                    .rxrfto(0b1)  // Receiver Frame Wait Timeout
+                    .ldeerr(0b1)  // Leading Edge Detection Processing Error
+                    .rxovrr(0b1)  // Receiver Overrun
+                    .rxpto(0b1)   // Preamble Detection Timeout
+                    .rxsfdto(0b1) // Receiver SFD Timeout
+                    .rxrscs(0b1)  // Receiver Reed-Solomon Correction Status
+                    .rxprej(0b1)  // Receiver Preamble Rejection
+            )?;
+
+        Ok(())
+    }
 }
 
-impl<SPI, CS, State> DW1000<SPI, CS, State>
-    where
-        SPI: spi::Transfer<u8> + spi::Write<u8>,
-        CS:  OutputPin,
-{
-    /// Returns the TX antenna delay
-    pub fn get_tx_antenna_delay(&mut self)
-        -> Result<Duration, Error<SPI, CS>>
+impl<SPI, CS, State> DW1000<SPI, CS, State>
+    where
+        SPI: spi::Transfer<u8> + spi::Write<u8>,
+        CS:  OutputPin,
+{
+    /// Checks that the DW1000 is present and responding
+    ///
+    /// Reads the device identifier and verifies that it matches the DW1000.
+    /// Returns [`Error::NoResponse`], if the value read back consists of only
+    /// zeros or only ones, which typically points to a wiring or power supply
+    /// problem. Returns [`Error::InvalidDeviceId`], if another value is read.
+    ///
+    /// [`DW1000::init`] calls this automatically.
+    pub fn check_device_id(&mut self) -> Result<(), Error<SPI, CS>> {
+        let dev_id = self.ll.dev_id().read()?;
+
+        let all_zeros = dev_id.ridtag() == 0x0000 && dev_id.model() == 0x00
+            && dev_id.ver() == 0x0 && dev_id.rev() == 0x0;
+        let all_ones = dev_id.ridtag() == 0xFFFF && dev_id.model() == 0xFF
+            && dev_id.ver() == 0xF && dev_id.rev() == 0xF;
+        if all_zeros || all_ones {
+            return Err(Error::NoResponse);
+        }
+
+        // See user manual, section 7.2.2.
+        if dev_id.ridtag() != 0xDECA || dev_id.model() != 0x01 {
+            return Err(Error::InvalidDeviceId);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the TX antenna delay
+    pub fn get_tx_antenna_delay(&mut self)
+        -> Result<Duration, Error<SPI, CS>>
+    {
+        let tx_antenna_delay = self.ll.tx_antd().read()?.value();
+
+        // Since `tx_antenna_delay` is `u16`, the following will never panic.
+        let tx_antenna_delay = Duration::new(tx_antenna_delay.into()).unwrap();
+
+        Ok(tx_antenna_delay)
+    }
+
+    /// Returns the RX antenna delay
+    pub fn get_rx_antenna_delay(&mut self)
+        -> Result<Duration, Error<SPI, CS>>
+    {
+        let rx_antenna_delay = self.ll.lde_rxantd().read()?.value();
+
+        // Since `rx_antenna_delay` is `u16`, the following will never panic.
+        let rx_antenna_delay = Duration::new(rx_antenna_delay.into()).unwrap();
+
+        Ok(rx_antenna_delay)
+    }
+
+    /// Returns the RX and TX antenna delays, in that order
+    ///
+    /// This mirrors [`DW1000::set_antenna_delay`].
+    pub fn get_antenna_delays(&mut self)
+        -> Result<(Duration, Duration), Error<SPI, CS>>
+    {
+        let rx_antenna_delay = self.get_rx_antenna_delay()?;
+        let tx_antenna_delay = self.get_tx_antenna_delay()?;
+
+        Ok((rx_antenna_delay, tx_antenna_delay))
+    }
+
+    /// Returns the network id and address used for sending and receiving
+    pub fn get_address(&mut self)
+        -> Result<mac::Address, Error<SPI, CS>>
+    {
+        Ok(self.pan_address()?.into())
+    }
+
+    /// Returns the network id and address used for sending and receiving
+    ///
+    /// Works like [`DW1000::get_address`], but returns a [`PanAddress`].
+    pub fn pan_address(&mut self) -> Result<PanAddress, Error<SPI, CS>> {
+        let panadr = self.ll.panadr().read()?;
+
+        Ok(PanAddress {
+            pan_id:     mac::PanId(panadr.pan_id()),
+            short_addr: mac::ShortAddress(panadr.short_addr()),
+        })
+    }
+
+    /// Reads a snapshot of the node configuration
+    ///
+    /// The snapshot can be passed to [`DW1000::restore_config`] after a
+    /// [`DW1000::reset`], to restore the configuration in one step. See
+    /// [`Config`] for what is included.
+    pub fn read_config(&mut self) -> Result<Config, Error<SPI, CS>> {
+        let panadr = self.ll.panadr().read()?;
+
+        Ok(Config {
+            pan_id:           mac::PanId(panadr.pan_id()),
+            short_address:    mac::ShortAddress(panadr.short_addr()),
+            rx_antenna_delay: self.ll.lde_rxantd().read()?.value(),
+            tx_antenna_delay: self.ll.tx_antd().read()?.value(),
+            xtal_trim:        self.get_xtal_trim()?,
+        })
+    }
+
+    /// Returns the current system time
+    pub fn sys_time(&mut self) -> Result<Instant, Error<SPI, CS>> {
+        let sys_time = self.ll.sys_time().read()?.value();
+
+        // Since hardware timestamps fit within 40 bits, the following should
+        // never panic.
+        Ok(Instant::new(sys_time).unwrap())
+    }
+
+    /// Measures the latency of a register access
+    ///
+    /// Reads SYS_TIME twice in a row and returns the difference. This is the
+    /// time a single register access takes, including the SPI transfer and any
+    /// overhead on the host. SYS_TIME has a resolution of roughly 8 ns, which
+    /// limits the precision of the result. Since the latency depends on the
+    /// host and its SPI configuration, measuring it can help determine the
+    /// lead time required for delayed transmissions.
+    ///
+    /// To measure the full latency of a transmission, read
+    /// [`DW1000::sys_time`] before calling [`DW1000::send`], then compare the
+    /// result to [`DW1000::tx_time`], once [`DW1000::wait`] has returned. Any
+    /// delayed transmission needs to be scheduled at least that far in the
+    /// future.
+    pub fn measure_access_latency(&mut self)
+        -> Result<Duration, Error<SPI, CS>>
+    {
+        let before = self.sys_time()?;
+        let after  = self.sys_time()?;
+
+        Ok(after.duration_since(before))
+    }
+
+    /// Reads back the transmit frame control settings
+    ///
+    /// Decodes TX_FCTRL, which is configured by [`DW1000::send`]. This can be
+    /// used to verify that the intended settings were programmed, for example
+    /// when debugging a mismatch with another node. See user manual, section
+    /// 7.2.10.
+    pub fn read_tx_frame_control(&mut self)
+        -> Result<TxFrameControl, Error<SPI, CS>>
+    {
+        let tx_fctrl = self.ll.tx_fctrl().read()?;
+        let preamble = tx_fctrl.txpsr() << 2 | tx_fctrl.pe();
+
+        Ok(TxFrameControl {
+            frame_len:
+                tx_fctrl.tflen() as u16 | (tx_fctrl.tfle() as u16) << 7,
+            bitrate:
+                BitRate::from_bits(tx_fctrl.txbr()),
+            ranging_enable:
+                tx_fctrl.tr() == 0b1,
+            pulse_repetition_frequency:
+                PulseRepetitionFrequency::from_bits(tx_fctrl.txprf()),
+            preamble_length:
+                PreambleLength::from_bits(preamble),
+            buffer_offset:
+                tx_fctrl.txboffs(),
+            ifs_delay:
+                tx_fctrl.ifsdelay(),
+        })
+    }
+
+    /// Indicates whether the last frame was received at the TX bit rate
+    ///
+    /// Compares the bit rate of the last received frame (RXBR in RX_FINFO)
+    /// with the bit rate configured for transmission (TXBR in TX_FCTRL). A
+    /// mismatch indicates that a peer is configured differently, which can
+    /// explain why frames from this node aren't received by it. See user
+    /// manual, sections 7.2.10 and 7.2.18.
+    pub fn last_rx_matches_tx(&mut self) -> Result<bool, Error<SPI, CS>> {
+        let rx_bit_rate = self.ll.rx_finfo().read()?.rxbr();
+        let tx_bit_rate = self.ll.tx_fctrl().read()?.txbr();
+
+        Ok(rx_bit_rate == tx_bit_rate)
+    }
+
+    /// Reads back the frame filtering configuration
+    ///
+    /// Decodes the frame filtering bits of SYS_CFG, which are configured by
+    /// [`DW1000::receive`], according to [`RxConfig::frame_filtering`]. This
+    /// can be used to check whether the receiver is in promiscuous or filtered
+    /// mode. See user manual, section 7.2.6.
+    pub fn read_frame_filter(&mut self)
+        -> Result<FrameFilter, Error<SPI, CS>>
+    {
+        let sys_cfg = self.ll.sys_cfg().read()?;
+
+        Ok(FrameFilter {
+            enabled:           sys_cfg.ffen() == 0b1,
+            coordinator:       sys_cfg.ffbc() == 0b1,
+            allow_beacon:      sys_cfg.ffab() == 0b1,
+            allow_data:        sys_cfg.ffad() == 0b1,
+            allow_ack:         sys_cfg.ffaa() == 0b1,
+            allow_mac_command: sys_cfg.ffam() == 0b1,
+            allow_reserved:    sys_cfg.ffar() == 0b1,
+            allow_type_4:      sys_cfg.ffa4() == 0b1,
+            allow_type_5:      sys_cfg.ffa5() == 0b1,
+        })
+    }
+
+    /// Returns statistics about failed receive operations
+    ///
+    /// These statistics are based on the event counters in the DIG_DIAG
+    /// register set. The event counters need to be enabled for this to work.
+    /// Use [`DW1000::reset_receive_failure_stats`] to do that, and to reset
+    /// the counters between test runs.
+    ///
+    /// Please note that [`DW1000::send`] currently resets the event counters,
+    /// so the statistics only cover the time since the last send operation.
+    pub fn receive_failure_stats(&mut self)
+        -> Result<ReceiveFailureStats, Error<SPI, CS>>
+    {
+        Ok(ReceiveFailureStats {
+            sfd_timeouts:        self.ll.evc_sto().read()?.value(),
+            preamble_timeouts:   self.ll.evc_pto().read()?.value(),
+            frame_wait_timeouts: self.ll.evc_fwto().read()?.value(),
+            overruns:            self.ll.evc_ovr().read()?.value(),
+        })
+    }
+
+    /// Resets and enables the event counters
+    ///
+    /// See [`DW1000::receive_failure_stats`].
+    pub fn reset_receive_failure_stats(&mut self)
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.ll.evc_ctrl().write(|w| w.evc_clr(0b1))?;
+        while self.ll.evc_ctrl().read()?.evc_clr() == 0b1 {}
+
+        self.ll.evc_ctrl().write(|w| w.evc_en(0b1))?;
+
+        Ok(())
+    }
+
+    /// Returns the number of accumulated preamble symbols of the last frame
+    ///
+    /// This is the RXPACC field from RX_FINFO. It reports how many preamble
+    /// symbols were accumulated to estimate the channel impulse response, and
+    /// is required to interpret the receive quality diagnostics. See user
+    /// manual, section 7.2.18.
+    pub fn rx_preamble_count(&mut self) -> Result<u16, Error<SPI, CS>> {
+        Ok(self.ll.rx_finfo().read()?.rxpacc())
+    }
+
+    /// Returns the standard deviation of the noise of the last received frame
+    ///
+    /// This is the STD_NOISE field from RX_FQUAL. It is measured from the
+    /// accumulator (the channel impulse response), and can be used to derive
+    /// detection thresholds, for example to evaluate the accumulator data with
+    /// a custom leading edge detection algorithm. See user manual, section
+    /// 7.2.20.
+    pub fn noise_std(&mut self) -> Result<u16, Error<SPI, CS>> {
+        Ok(self.ll.rx_fqual().read()?.std_noise())
+    }
+
+    /// Returns the first path index of the last received frame
+    ///
+    /// This is the FP_INDEX field from RX_TIME, which indicates the position of
+    /// the leading edge within the accumulator (the channel impulse response).
+    /// It is a fixed-point value with 6 fractional bits, meaning the integer
+    /// part is the index of the accumulator sample, and the fractional part
+    /// the position between it and the next sample. See user manual, section
+    /// 7.2.23.
+    ///
+    /// Requires the `float` feature. See [`DW1000::first_path_index_fixed`]
+    /// for a variant that doesn't use floating-point math.
+    #[cfg(feature = "float")]
+    pub fn first_path_index(&mut self) -> Result<f32, Error<SPI, CS>> {
+        let fp_index = self.first_path_index_fixed()?;
+        Ok(fp_index as f32 / 64.0)
+    }
+
+    /// Returns the first path index of the last received frame, as raw
+    /// fixed-point value
+    ///
+    /// Returns the FP_INDEX field from RX_TIME unchanged. The lower 6 bits are
+    /// the fractional part, so dividing by 64 yields the sample position. See
+    /// [`DW1000::first_path_index`] for details.
+    pub fn first_path_index_fixed(&mut self) -> Result<u16, Error<SPI, CS>> {
+        Ok(self.ll.rx_time().read()?.fp_index())
+    }
+
+    /// Indicates whether the preamble accumulation of the last frame saturated
+    ///
+    /// Compares the accumulated preamble symbol count against the length of
+    /// the preamble that was sent. If the whole preamble was accumulated, the
+    /// signal power and first path estimates derived from the accumulator are
+    /// unreliable, and measurements based on them should be discarded.
+    pub fn is_preamble_accumulation_saturated(&mut self,
+        preamble_length: PreambleLength,
+    )
+        -> Result<bool, Error<SPI, CS>>
+    {
+        let rxpacc = self.rx_preamble_count()?;
+        Ok(rxpacc >= preamble_length.get_symbol_count())
+    }
+
+    /// Indicates whether a transmission has finished
+    ///
+    /// Reads the TXFRS bit from SYS_STATUS, without clearing it or any other
+    /// status bits. Unlike [`DW1000::wait`], this has no side effects, which
+    /// makes it suitable for checking status from an interrupt handler. Use
+    /// [`DW1000::clear_status`] to clear the status bits when you're ready.
+    pub fn is_transmit_done(&mut self) -> Result<bool, Error<SPI, CS>> {
+        Ok(self.read_status_partial()?.txfrs() == 0b1)
+    }
+
+    /// Indicates whether a received frame is ready
+    ///
+    /// Reads the RXDFR and LDEDONE bits from SYS_STATUS, without clearing them
+    /// or any other status bits. See [`DW1000::is_transmit_done`].
+    pub fn is_frame_ready(&mut self) -> Result<bool, Error<SPI, CS>> {
+        let sys_status = self.read_status_partial()?;
+        Ok(sys_status.rxdfr() == 0b1 && sys_status.ldedone() == 0b1)
+    }
+
+    /// Reads only the low 2 bytes of SYS_STATUS
+    ///
+    /// These contain the TX status bits, and the most important RX status bits
+    /// (including RXDFR and LDEDONE), but none of the RX error bits. Reading
+    /// them requires fewer SPI bytes than reading the full register, which
+    /// reduces latency when busily polling over a slow SPI bus. Use
+    /// `ll().sys_status()` to read the full register.
+    pub fn read_status_partial(&mut self)
+        -> Result<ll::sys_status_lo::R, Error<SPI, CS>>
+    {
+        Ok(self.ll.sys_status_lo().read()?)
+    }
+
+    /// Reads the interrupt mask
+    ///
+    /// Returns the contents of SYS_MASK, which determine which SYS_STATUS
+    /// events assert the IRQ output. This can be used in an interrupt handler,
+    /// to find out which of the status bits caused the interrupt. See user
+    /// manual, section 7.2.16.
+    pub fn read_interrupt_mask(&mut self)
+        -> Result<ll::sys_mask::R, Error<SPI, CS>>
+    {
+        Ok(self.ll.sys_mask().read()?)
+    }
+
+    /// Indicates whether an ACK frame has been sent automatically
+    ///
+    /// Returns `true`, if a received frame triggered automatic acknowledgement
+    /// (AAT) and the ACK frame has been sent (TXFRS). Like
+    /// [`DW1000::is_transmit_done`], this doesn't clear any status bits. Use
+    /// [`DW1000::clear_status`] to clear AAT and TXFRS once you've handled the
+    /// event.
+    pub fn is_auto_ack_sent(&mut self) -> Result<bool, Error<SPI, CS>> {
+        let sys_status = self.ll.sys_status().read()?;
+        Ok(sys_status.aat() == 0b1 && sys_status.txfrs() == 0b1)
+    }
+
+    /// Clears the selected SYS_STATUS bits
+    ///
+    /// Status bits are cleared by writing `1` to them. Select the bits to clear
+    /// by setting them to `0b1` in the closure. Bits that aren't set are left
+    /// untouched.
+    pub fn clear_status<F>(&mut self, f: F) -> Result<(), Error<SPI, CS>>
+        where
+            F: FnOnce(&mut ll::sys_status::W) -> &mut ll::sys_status::W,
+    {
+        self.ll.sys_status().write(f)?;
+        Ok(())
+    }
+
+    /// Clears all SYS_STATUS bits
+    ///
+    /// Writes `1` to every status bit that can be cleared, including those in
+    /// the upper byte (RXRSCS, RXPREJ, TXPUTE). The read-only bits (IRQS,
+    /// HSRBP, ICRBP) are not affected. This is useful to get a clean slate,
+    /// when recovering from an unknown state. See [`DW1000::clear_status`] to
+    /// only clear selected bits.
+    pub fn clear_all_status(&mut self) -> Result<(), Error<SPI, CS>> {
+        self.clear_status(|w|
+            w
+                .cplock(0b1)
+                .esyncr(0b1)
+                .aat(0b1)
+                .txfrb(0b1)
+                .txprs(0b1)
+                .txphs(0b1)
+                .txfrs(0b1)
+                .rxprd(0b1)
+                .rxsfdd(0b1)
+                .ldedone(0b1)
+                .rxphd(0b1)
+                .rxphe(0b1)
+                .rxdfr(0b1)
+                .rxfcg(0b1)
+                .rxfce(0b1)
+                .rxrfsl(0b1)
+                .rxrfto(0b1)
+                .ldeerr(0b1)
+                .rxovrr(0b1)
+                .rxpto(0b1)
+                .gpioirq(0b1)
+                .slp2init(0b1)
+                .rfpll_ll(0b1)
+                .clkpll_ll(0b1)
+                .rxsfdto(0b1)
+                .hpdwarn(0b1)
+                .txberr(0b1)
+                .affrej(0b1)
+                .rxrscs(0b1)
+                .rxprej(0b1)
+                .txpute(0b1)
+        )
+    }
+
+    /// Clears the TX status bits
+    ///
+    /// Clears TXFRB, TXPRS, TXPHS and TXFRS, the bits that track the progress
+    /// of a transmission (see user manual, section 7.2.17). [`DW1000::wait`]
+    /// does this automatically once a frame has been sent. Transmit flows that
+    /// don't go through the `Sending` state can use this method to reset the
+    /// status before the next transmission.
+    pub fn clear_tx_status(&mut self) -> Result<(), Error<SPI, CS>> {
+        self.clear_status(|w|
+            w
+                .txfrb(0b1) // Transmit Frame Begins
+                .txprs(0b1) // Transmit Preamble Sent
+                .txphs(0b1) // Transmit PHY Header Sent
+                .txfrs(0b1) // Transmit Frame Sent
+        )
+    }
+
+    /// Toggles the host side receive buffer pointer
+    ///
+    /// Only relevant if double buffering is enabled (by clearing DIS_DRXB in
+    /// SYS_CFG). Writes the HRBPT bit in SYS_CTRL, which switches the host
+    /// side to the other receive buffer, and releases the current one to the
+    /// receiver.
+    ///
+    /// The user manual (section 4.3) requires the following sequence, after a
+    /// frame has been read from the current buffer:
+    ///
+    /// 1. Check for an overrun (RXOVRR in SYS_STATUS). If one occurred, the
+    ///    buffer contents are not reliable, and the receiver must be reset.
+    /// 2. Clear the receive event status bits for the frame that was read.
+    /// 3. Toggle the buffer pointer using this method.
+    /// 4. Compare [`DW1000::host_buffer_pointer`] with ICRBP in SYS_STATUS.
+    ///    If they're equal, another frame has already been received into the
+    ///    new buffer, and can be processed right away.
+    pub fn swap_rx_buffer(&mut self) -> Result<(), Error<SPI, CS>> {
+        // Use `write` instead of `modify`, to not trigger any other actions
+        // controlled by SYS_CTRL.
+        self.ll.sys_ctrl().write(|w| w.hrbpt(0b1))?;
+        Ok(())
+    }
+
+    /// Returns the state of the host side receive buffer pointer
+    ///
+    /// This is the HSRBP bit in SYS_STATUS. See [`DW1000::swap_rx_buffer`].
+    pub fn host_buffer_pointer(&mut self) -> Result<bool, Error<SPI, CS>> {
+        Ok(self.ll.sys_status().read()?.hsrbp() == 0b1)
+    }
+
+    /// Returns the current state of the DW1000's internal state machines
+    ///
+    /// This can be used to detect whether the DW1000 got stuck in some state,
+    /// for example the receiver not returning to idle.
+    pub fn sys_state(&mut self) -> Result<SysState, Error<SPI, CS>> {
+        let sys_state = self.ll.sys_state().read()?;
+
+        Ok(SysState {
+            pmsc: PmscState::from_raw(sys_state.pmsc_state()),
+            rx:   sys_state.rx_state(),
+            tx:   sys_state.tx_state(),
+        })
+    }
+
+    /// Indicates whether the receiver is currently enabled
+    ///
+    /// Returns `true`, if the receive state machine in SYS_STATE isn't idle.
+    /// See [`DW1000::sys_state`].
+    pub fn is_receiver_enabled(&mut self) -> Result<bool, Error<SPI, CS>> {
+        Ok(!self.sys_state()?.rx_idle())
+    }
+
+    /// Indicates whether the transmitter is currently enabled
+    ///
+    /// Returns `true`, if the transmit state machine in SYS_STATE isn't idle.
+    /// See [`DW1000::sys_state`].
+    pub fn is_transmitter_enabled(&mut self) -> Result<bool, Error<SPI, CS>> {
+        Ok(!self.sys_state()?.tx_idle())
+    }
+
+    /// Reads the DW1000's key registers for diagnostic purposes
+    ///
+    /// The returned [`StateDump`] implements `Debug`, and is intended to be
+    /// printed and attached to bug reports.
+    pub fn dump_state(&mut self) -> Result<StateDump, Error<SPI, CS>> {
+        Ok(StateDump {
+            dev_id:     self.ll.dev_id().read()?,
+            sys_cfg:    self.ll.sys_cfg().read()?,
+            sys_status: self.ll.sys_status().read()?,
+            sys_state:  self.ll.sys_state().read()?,
+            chan_ctrl:  self.ll.chan_ctrl().read()?,
+            tx_fctrl:   self.ll.tx_fctrl().read()?,
+            rx_finfo:   self.ll.rx_finfo().read()?,
+
+            evc_phe:  self.ll.evc_phe().read()?.value(),
+            evc_rse:  self.ll.evc_rse().read()?.value(),
+            evc_fcg:  self.ll.evc_fcg().read()?.value(),
+            evc_fce:  self.ll.evc_fce().read()?.value(),
+            evc_ffr:  self.ll.evc_ffr().read()?.value(),
+            evc_ovr:  self.ll.evc_ovr().read()?.value(),
+            evc_sto:  self.ll.evc_sto().read()?.value(),
+            evc_pto:  self.ll.evc_pto().read()?.value(),
+            evc_fwto: self.ll.evc_fwto().read()?.value(),
+            evc_txfs: self.ll.evc_txfs().read()?.value(),
+            evc_hpw:  self.ll.evc_hpw().read()?.value(),
+            evc_tpw:  self.ll.evc_tpw().read()?.value(),
+        })
+    }
+
+    /// Returns a simplified view of what the transceiver is currently doing
+    ///
+    /// This is based on [`DW1000::sys_state`], and can be used by a watchdog to
+    /// detect a transceiver that is stuck in one state for too long.
+    pub fn transceiver_state(&mut self)
+        -> Result<TransceiverState, Error<SPI, CS>>
+    {
+        Ok(self.sys_state()?.transceiver_state())
+    }
+
+    /// Indicates whether both PLLs are locked
+    ///
+    /// See [`DW1000::pll_status`] for details.
+    pub fn pll_locked(&mut self) -> Result<bool, Error<SPI, CS>> {
+        let status = self.pll_status()?;
+        Ok(status.clock_pll_locked && status.rf_pll_locked)
+    }
+
+    /// Returns the lock status of the clock PLL and the RF PLL
+    ///
+    /// Checks CPLOCK, CLKPLL_LL, and RFPLL_LL in SYS_STATUS. Losing lock is
+    /// often caused by using a too fast SPI clock before the clock PLL locked,
+    /// or by power supply problems, and typically requires the DW1000 to be
+    /// reset. See user manual, section 7.2.17.
+    ///
+    /// All three flags are sticky. CPLOCK is set when the clock PLL locks, and
+    /// the losing lock flags are set when a PLL loses lock. Once the problem
+    /// has been dealt with, clear the losing lock flags to detect further
+    /// problems:
+    ///
+    /// ``` rust,ignore
+    /// dw1000.clear_status(|w| w.clkpll_ll(0b1).rfpll_ll(0b1))?;
+    /// ```
+    ///
+    /// Please note that clearing CPLOCK makes the clock PLL look unlocked,
+    /// until it locks again. [`DW1000::clear_all_status`] does that.
+    pub fn pll_status(&mut self) -> Result<PllStatus, Error<SPI, CS>> {
+        let sys_status = self.ll.sys_status().read()?;
+
+        Ok(PllStatus {
+            clock_pll_locked:
+                sys_status.cplock() == 0b1 && sys_status.clkpll_ll() == 0b0,
+            rf_pll_locked:
+                sys_status.rfpll_ll() == 0b0,
+        })
+    }
+
+    /// Waits until both PLLs are locked, with a software timeout
+    ///
+    /// Busily polls [`DW1000::pll_locked`], until it indicates that both PLLs
+    /// are locked, or `timer` expires. The timer must already be started.
+    /// Returns [`Error::Timeout`], if the timer expired before the PLLs
+    /// locked.
+    ///
+    /// CPLOCK is only set when the clock PLL locks. [`DW1000::set_channel`]
+    /// clears it after changing the channel, and then uses this method to
+    /// wait for the PLLs to settle. If CPLOCK was cleared while the PLL stayed
+    /// locked, for example by [`DW1000::clear_all_status`], this times out.
+    pub fn wait_for_pll_lock<T>(&mut self, timer: &mut T)
+        -> Result<(), Error<SPI, CS>>
+        where
+            T: CountDown,
+    {
+        while !self.pll_locked()? {
+            if timer.wait().is_ok() {
+                return Err(Error::Timeout);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Indicates whether the accumulator memory clock is enabled
+    ///
+    /// Checks FACE and AMCE in PMSC_CTRL0. See user manual, section 7.2.50.1.
+    pub fn is_accumulator_clock_enabled(&mut self)
+        -> Result<bool, Error<SPI, CS>>
+    {
+        let pmsc_ctrl0 = self.ll.pmsc_ctrl0().read()?;
+        Ok(pmsc_ctrl0.face() == 0b1 && pmsc_ctrl0.amce() == 0b1)
+    }
+
+    /// Runs a closure with the accumulator memory clock enabled
+    ///
+    /// The accumulator memory (ACC_MEM) can only be read while its clock is
+    /// enabled, but leaving the clock on wastes power. This enables the clock,
+    /// runs `f`, then restores the previous clock configuration, and returns
+    /// the closure's result. See user manual, section 7.2.50.1.
+    pub fn with_accumulator_clock<F, R>(&mut self, f: F)
+        -> Result<R, Error<SPI, CS>>
+        where
+            F: FnOnce(&mut Self) -> R,
+    {
+        let pmsc_ctrl0 = self.ll.pmsc_ctrl0().read()?;
+
+        self.ll
+            .pmsc_ctrl0()
+            .modify(|_, w|
+                w
+                    .rxclks(0b10) // force 125 MHz PLL clock
+                    .face(0b1)
+                    .amce(0b1)
+            )?;
+
+        let result = f(self);
+
+        self.ll
+            .pmsc_ctrl0()
+            .modify(|_, w|
+                w
+                    .rxclks(pmsc_ctrl0.rxclks())
+                    .face(pmsc_ctrl0.face())
+                    .amce(pmsc_ctrl0.amce())
+            )?;
+
+        Ok(result)
+    }
+
+    /// Reads a 32-bit word from OTP memory
+    ///
+    /// See user manual, section 6.3.3.
+    pub fn read_otp(&mut self, address: u16) -> Result<u32, Error<SPI, CS>> {
+        self.ll.otp_addr().write(|w| w.value(address))?;
+        self.ll.otp_ctrl().modify(|_, w|
+            w
+                .otprden(0b1)
+                .otpread(0b1)
+        )?;
+        while self.ll.otp_ctrl().read()?.otpread() == 0b1 {}
+
+        let value = self.ll.otp_rdat().read()?.value();
+        Ok(value)
+    }
+
+    /// Reads the factory antenna delay for the channel and PRF from OTP
+    ///
+    /// Returns `None`, if no value was programmed for the channel and PRF.
+    fn read_factory_antenna_delay(&mut self,
+        channel: UwbChannel,
+        prf:     PulseRepetitionFrequency,
+    )
+        -> Result<Option<u16>, Error<SPI, CS>>
+    {
+        // See user manual, section 6.3.2, table 10.
+        let address = match channel {
+            UwbChannel::Channel2 => 0x01C,
+            UwbChannel::Channel5 => 0x01D,
+            _                    => return Ok(None),
+        };
+
+        let delays = self.read_otp(address)?;
+        let delay = match prf {
+            PulseRepetitionFrequency::Mhz16 => delays & 0xffff,
+            PulseRepetitionFrequency::Mhz64 => delays >> 16,
+        } as u16;
+
+        if delay == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(delay))
+    }
+
+    /// Reads the temperature of the DW1000
+    ///
+    /// Returns the temperature in hundredths of a degree Celsius. The reading
+    /// is calibrated using the measurement at 23 °C that is programmed into
+    /// OTP during production. See user manual, section 6.4.
+    pub fn read_temperature(&mut self) -> Result<i32, Error<SPI, CS>> {
+        // Enable the sensor biases and outputs, then start a conversion. The
+        // conversion finishes faster than the next SPI transaction.
+        self.ll.rf_sensor_bias().write(|w| w.value(0x80))?;
+        self.ll.rf_sensor_ctrl().write(|w| w.value(0x0a))?;
+        self.ll.rf_sensor_ctrl().write(|w| w.value(0x0f))?;
+        self.ll.tc_sarc().write(|w| w.sar_ctrl(0b0))?;
+        self.ll.tc_sarc().write(|w| w.sar_ctrl(0b1))?;
+
+        let raw = self.ll.tc_sarl().read()?.sar_ltemp();
+
+        self.ll.tc_sarc().write(|w| w.sar_ctrl(0b0))?;
+
+        // The temperature measured at 23 °C is in the low byte of OTP address
+        // 0x009. One step of the reading equals 1.14 °C.
+        let reference = self.read_otp(0x009)? as u8;
+        Ok((raw as i32 - reference as i32) * 114 + 2300)
+    }
+
+    /// Adjusts an antenna delay for the current temperature
+    ///
+    /// Reads the temperature (see [`DW1000::read_temperature`]), and adjusts
+    /// `base_delay` by 5.35 ps per °C, relative to a calibration temperature
+    /// of 23 °C, following Decawave's application note APS014. `base_delay` is
+    /// the antenna delay calibrated at 23 °C, in DW1000 time units.
+    ///
+    /// The result can be passed to [`DW1000::set_antenna_delay`]. Doing this
+    /// periodically keeps ranging accurate over a wide temperature range.
+    pub fn temperature_adjusted_antenna_delay(&mut self, base_delay: u16)
+        -> Result<u16, Error<SPI, CS>>
     {
-        let tx_antenna_delay = self.ll.tx_antd().read()?.value();
+        // 5.35 ps per °C is 0.535 ps per 0.01 °C. One DW1000 time unit is
+        // 1000 / 63.8976 ps, so this is 0.535 * 0.0638976 time units.
+        const NUMERATOR:   i64 = 535 * 638_976;
+        const DENOMINATOR: i64 = 1_000 * 10_000_000;
 
-        // Since `tx_antenna_delay` is `u16`, the following will never panic.
-        let tx_antenna_delay = Duration::new(tx_antenna_delay.into()).unwrap();
+        let delta = (self.read_temperature()? - 2300) as i64;
+        let delay = base_delay as i64
+            + (delta * NUMERATOR + DENOMINATOR / 2).div_euclid(DENOMINATOR);
 
-        Ok(tx_antenna_delay)
+        Ok(delay.max(0).min(u16::MAX as i64) as u16)
     }
 
-    /// Returns the network id and address used for sending and receiving
-    pub fn get_address(&mut self)
-        -> Result<mac::Address, Error<SPI, CS>>
-    {
-        let panadr = self.ll.panadr().read()?;
+    /// Loads the LDOTUNE value from OTP, if it was programmed
+    ///
+    /// See user manual, section 2.5.5.11.
+    fn load_ldotune(&mut self) -> Result<(), Error<SPI, CS>> {
+        let ldotune_low = self.read_otp(0x004)?;
+        if ldotune_low != 0 {
+            let ldotune_high = self.read_otp(0x005)?;
+
+            let ldotune = ldotune_low as u64 | (ldotune_high as u64) << 32;
+            self.ll.ldotune().write(|w| w.value(ldotune))?;
+        }
 
-        Ok(mac::Address::Short(
-            mac::PanId(panadr.pan_id()),
-            mac::ShortAddress(panadr.short_addr()),
-        ))
+        Ok(())
     }
 
-    /// Returns the current system time
-    pub fn sys_time(&mut self) -> Result<Instant, Error<SPI, CS>> {
-        let sys_time = self.ll.sys_time().read()?.value();
+    /// Sets the crystal trim value
+    ///
+    /// Trimming the crystal adjusts the carrier frequency, which can be used
+    /// to reduce the frequency offset between nodes. Only the lower 5 bits of
+    /// `trim` are used. Each step changes the frequency by roughly 1.5 ppm.
+    pub fn set_xtal_trim(&mut self, trim: u8) -> Result<(), Error<SPI, CS>> {
+        self.ll
+            .fs_xtalt()
+            .write(|w|
+                w
+                    .xtalt(trim & 0b11111)
+                    .reserved(0b011)
+            )?;
 
-        // Since hardware timestamps fit within 40 bits, the following should
-        // never panic.
-        Ok(Instant::new(sys_time).unwrap())
+        Ok(())
+    }
+
+    /// Returns the crystal trim value
+    pub fn get_xtal_trim(&mut self) -> Result<u8, Error<SPI, CS>> {
+        Ok(self.ll.fs_xtalt().read()?.xtalt())
+    }
+
+    /// Loads the factory crystal trim value from OTP and applies it
+    ///
+    /// If no trim value was programmed into OTP, the mid-range value (`0x10`)
+    /// is used instead. Returns the trim value that was applied.
+    pub fn load_factory_xtal_trim(&mut self) -> Result<u8, Error<SPI, CS>> {
+        let mut trim = (self.read_otp(0x01E)? & 0b11111) as u8;
+        if trim == 0 {
+            trim = 0x10;
+        }
+
+        self.set_xtal_trim(trim)?;
+
+        Ok(trim)
     }
 
     /// Provides direct access to the register-level API
@@ -867,6 +3176,32 @@ impl<SPI, CS, State> DW1000<SPI, CS, State>
         &mut self.ll
     }
 
+    /// Indicates whether SYS_STATE shows anything other than an idle
+    /// transceiver
+    fn transceiver_active(&mut self) -> Result<bool, Error<SPI, CS>> {
+        let state = self.sys_state()?;
+
+        let active = !matches!(state.pmsc, PmscState::Init | PmscState::Idle);
+
+        Ok(active || !state.rx_idle() || !state.tx_idle())
+    }
+
+    /// Resets the receiver
+    fn reset_receiver(&mut self) -> Result<(), Error<SPI, CS>> {
+        self.ll
+            .pmsc_ctrl0()
+            .modify(|_, w|
+                w.softreset(0b1110) // reset receiver
+            )?;
+        self.ll
+            .pmsc_ctrl0()
+            .modify(|_, w|
+                w.softreset(0b1111) // clear reset
+            )?;
+
+        Ok(())
+    }
+
     /// Force the DW1000 into IDLE mode
     ///
     /// Any ongoing RX/TX operations will be aborted.
@@ -878,6 +3213,31 @@ impl<SPI, CS, State> DW1000<SPI, CS, State>
 
         Ok(())
     }
+
+    /// Force the DW1000 into IDLE mode, with a software timeout
+    ///
+    /// Any ongoing RX/TX operations will be aborted. Unlike the methods that
+    /// force the DW1000 into IDLE mode internally, this doesn't wait forever,
+    /// if the DW1000 fails to confirm the transition. Instead, it returns
+    /// [`Error::Timeout`], once `timer` expires. The timer must already be
+    /// started.
+    ///
+    /// This allows robust firmware to detect an unresponsive DW1000 and
+    /// recover, for example by resetting it.
+    pub fn force_idle_timeout<T>(&mut self, timer: &mut T)
+        -> Result<(), Error<SPI, CS>>
+        where
+            T: CountDown,
+    {
+        self.ll.sys_ctrl().write(|w| w.trxoff(0b1))?;
+        while self.ll.sys_ctrl().read()?.trxoff() == 0b1 {
+            if timer.wait().is_ok() {
+                return Err(Error::Timeout);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 // Can't be derived without putting requirements on `SPI` and `CS`.
@@ -909,6 +3269,28 @@ pub enum Error<SPI, CS>
     /// PHY header error
     Phy,
 
+    /// Receiver FCS error, with the corrupted frame kept
+    ///
+    /// Returned instead of [`Error::Fcs`], if
+    /// [`RxConfig::keep_corrupted_frames`] is enabled. The first `len` bytes
+    /// of the buffer passed to [`DW1000::wait`] contain the corrupted frame,
+    /// including the FCS.
+    CorruptedFcs {
+        /// The length of the corrupted frame in the buffer
+        len: usize,
+    },
+
+    /// PHY header error, with the corrupted frame kept
+    ///
+    /// Returned instead of [`Error::Phy`], if
+    /// [`RxConfig::keep_corrupted_frames`] is enabled. The first `len` bytes
+    /// of the buffer passed to [`DW1000::wait`] contain whatever was received.
+    /// Since the PHY header holds the frame length, `len` itself may be wrong.
+    CorruptedPhy {
+        /// The length of the corrupted frame in the buffer
+        len: usize,
+    },
+
     /// Buffer too small
     BufferTooSmall {
         /// Indicates how large a buffer would have been required
@@ -922,6 +3304,12 @@ pub enum Error<SPI, CS>
     FrameWaitTimeout,
 
     /// Receiver Overrun
+    ///
+    /// A frame was lost, because the receiver overwrote a buffer the host had
+    /// not read yet. As required by the user manual (section 4.3), the
+    /// receiver has already been reset when this error is returned, so it's
+    /// safe to call `finish_receiving` and start a new receive operation. See
+    /// [`ReceiveFailureStats::overruns`] to track how often this happens.
     Overrun,
 
     /// Preamble Detection Timeout
@@ -959,6 +3347,45 @@ pub enum Error<SPI, CS>
 
     /// The configuration was not valid. Some combinations of settings are not allowed.
     InvalidConfiguration,
+
+    /// The operation did not finish in time
+    Timeout,
+
+    /// The DW1000 didn't respond
+    ///
+    /// Reading the device identifier returned only zeros or only ones. This
+    /// typically indicates a wiring or power supply problem.
+    NoResponse,
+
+    /// The device identifier doesn't match the DW1000
+    InvalidDeviceId,
+
+    /// The received frame is shorter than the configured minimum length
+    ///
+    /// See [`RxConfig::min_frame_len`].
+    FrameTooShort,
+
+    /// The received frame isn't addressed to any of the accepted addresses
+    ///
+    /// See [`DW1000::wait_filtered`].
+    NotForUs,
+
+    /// A PLL lost its lock, so a frame can't be sent reliably
+    ///
+    /// This is often caused by using a too fast SPI clock during
+    /// initialization, or by power supply problems. The DW1000 typically needs
+    /// to be reset to recover. See [`DW1000::pll_status`].
+    PllNotLocked,
+
+    /// The frame is too long for the configured PHR mode
+    ///
+    /// See [`DW1000::set_phr_mode`].
+    FrameTooLong,
+
+    /// The data doesn't fit into the 1024-byte transmit buffer
+    ///
+    /// See [`DW1000::set_tx_buffer_offset`] and [`DW1000::send_raw`].
+    TxBufferOverflow,
 }
 
 impl<SPI, CS> From<ll::Error<SPI, CS>> for Error<SPI, CS>
@@ -999,6 +3426,10 @@ impl<SPI, CS> fmt::Debug for Error<SPI, CS>
                 write!(f, "Fcs"),
             Error::Phy =>
                 write!(f, "Phy"),
+            Error::CorruptedFcs { len } =>
+                write!(f, "CorruptedFcs {{ len: {:?} }}", len),
+            Error::CorruptedPhy { len } =>
+                write!(f, "CorruptedPhy {{ len: {:?} }}", len),
             Error::BufferTooSmall { required_len } =>
                 write!(
                     f,
@@ -1027,6 +3458,69 @@ impl<SPI, CS> fmt::Debug for Error<SPI, CS>
                 write!(f, "Ssmarshal({:?})", error),
             Error::InvalidConfiguration =>
                 write!(f, "InvalidConfiguration"),
+            Error::Timeout =>
+                write!(f, "Timeout"),
+            Error::NoResponse =>
+                write!(f, "NoResponse"),
+            Error::InvalidDeviceId =>
+                write!(f, "InvalidDeviceId"),
+            Error::FrameTooShort =>
+                write!(f, "FrameTooShort"),
+            Error::NotForUs =>
+                write!(f, "NotForUs"),
+            Error::PllNotLocked =>
+                write!(f, "PllNotLocked"),
+            Error::FrameTooLong =>
+                write!(f, "FrameTooLong"),
+            Error::TxBufferOverflow =>
+                write!(f, "TxBufferOverflow"),
+        }
+    }
+}
+
+
+/// An error, together with the instance that caused it
+///
+/// Returned by methods that consume the instance, so it isn't lost if they
+/// fail.
+pub type ErrorWithDriver<SPI, CS, State> =
+    (DW1000<SPI, CS, State>, Error<SPI, CS>);
+
+
+/// An error that can occur in [`DW1000::send_and_get_tx_time`]
+pub enum SendError<SPI, CS>
+    where
+        SPI: spi::Transfer<u8> + spi::Write<u8>,
+        CS:  OutputPin,
+{
+    /// Starting the transmission failed
+    ///
+    /// As with [`DW1000::send`], there is no instance to return in this case.
+    Start(Error<SPI, CS>),
+
+    /// An error occurred after the transmission had been started
+    ///
+    /// The instance is returned in the `Sending` state, so the caller can
+    /// retry or cancel the operation.
+    Send(DW1000<SPI, CS, Sending>, Error<SPI, CS>),
+}
+
+// We can't derive this implementation, for the same reasons as the one for
+// `Error`.
+impl<SPI, CS> fmt::Debug for SendError<SPI, CS>
+    where
+        SPI: spi::Transfer<u8> + spi::Write<u8>,
+        <SPI as spi::Transfer<u8>>::Error: fmt::Debug,
+        <SPI as spi::Write<u8>>::Error: fmt::Debug,
+        CS: OutputPin,
+        <CS as OutputPin>::Error: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SendError::Start(error) =>
+                write!(f, "Start({:?})", error),
+            SendError::Send(dw1000, error) =>
+                write!(f, "Send({:?}, {:?})", dw1000, error),
         }
     }
 }
@@ -1040,16 +3534,26 @@ pub struct Uninitialized;
 #[derive(Debug)]
 pub struct Ready;
 
+/// Indicates that the `DW1000` instance is sleeping
+#[derive(Debug)]
+pub struct Sleeping;
+
 /// Indicates that the `DW1000` instance is currently sending
 #[derive(Debug)]
 pub struct Sending {
     finished: bool,
+    tx_time:  Option<Instant>,
 }
 
 /// Indicates that the `DW1000` instance is currently receiving
 #[derive(Debug)]
 pub struct Receiving {
-    finished: bool,
+    finished:      bool,
+    buffer:        [u8; 127],
+    min_frame_len: usize,
+    lde_enabled:   bool,
+
+    keep_corrupted_frames: bool,
 }
 
 
@@ -1062,6 +3566,469 @@ pub struct Message<'l> {
     /// register.
     pub rx_time: Instant,
 
+    /// The raw time the message was received
+    ///
+    /// This is the time stamp before the antenna delay and the correction
+    /// from leading edge detection are applied. The difference to `rx_time`
+    /// reflects those adjustments.
+    pub rx_time_raw: Instant,
+
+    /// The bit rate the frame was received at (RXBR)
+    ///
+    /// `None`, if RX_FINFO contains the reserved value. See
+    /// [`DW1000::last_rx_matches_tx`].
+    pub rx_bit_rate: Option<BitRate>,
+
+    /// Indicates whether the ranging bit was set in the PHY header (RNG)
+    ///
+    /// This can be used to tell ranging frames apart from data frames without
+    /// parsing the payload. See user manual, section 7.2.18.
+    pub was_ranging_frame: bool,
+
     /// The MAC frame
+    ///
+    /// If the frame is secured, its payload starts after the auxiliary
+    /// security header.
     pub frame: mac::Frame<'l>,
+
+    /// The auxiliary security header, if the frame is secured
+    ///
+    /// The payload is left as it was received. Decrypting it and checking the
+    /// MIC is left to the application. See [`frame::decode`].
+    pub security: Option<AuxSecurityHeader>,
+
+    /// The raw bytes of the frame, without the 2-byte FCS
+    ///
+    /// This can be used for anything that needs to process the frame as it
+    /// was sent, like computing an application-level checksum or signature.
+    pub bytes: &'l [u8],
+}
+
+impl<'l> Message<'l> {
+    /// Indicates whether the sender requested an acknowledgement
+    ///
+    /// If automatic acknowledgement is not used, the ACK frame needs to be
+    /// sent in software. See [`DW1000::send_ack`].
+    pub fn needs_ack(&self) -> bool {
+        self.frame.header.ack_request
+    }
+
+    /// Indicates whether the frame was sent to the broadcast address
+    ///
+    /// See [`frame::is_broadcast`] for details.
+    ///
+    /// [`frame::is_broadcast`]: ../frame/fn.is_broadcast.html
+    pub fn is_broadcast(&self) -> bool {
+        self.frame.header.destination
+            .is_some_and(|address| frame::is_broadcast(&address))
+    }
+
+    /// Returns the IEEE 802.15.4 frame version of the frame
+    pub fn version(&self) -> mac::FrameVersion {
+        self.frame.header.version
+    }
+
+    /// Returns the PAN ID of the sender, if the frame has a source address
+    ///
+    /// If PAN ID compression is used, this is the destination PAN ID.
+    pub fn source_pan(&self) -> Option<mac::PanId> {
+        self.frame.header.source.map(|address| address.pan_id())
+    }
+
+    /// Returns the PAN ID of the destination, if the frame has one
+    pub fn destination_pan(&self) -> Option<mac::PanId> {
+        self.frame.header.destination.map(|address| address.pan_id())
+    }
+
+    /// Indicates whether the frame was sent within a single PAN
+    ///
+    /// Returns `true`, if the frame uses PAN ID compression, or if source and
+    /// destination PAN IDs are equal. Returns `false`, if either address is
+    /// missing.
+    pub fn is_intra_pan(&self) -> bool {
+        if self.frame.header.pan_id_compress {
+            return true;
+        }
+
+        match (self.source_pan(), self.destination_pan()) {
+            (Some(source), Some(destination)) => source == destination,
+            _                                 => false,
+        }
+    }
+}
+
+/// Decodes the frame length from RXFLEN and RXFLE
+///
+/// RXFLE is only non-zero in extended PHR mode.
+fn frame_len(rx_finfo: &ll::rx_finfo::R) -> usize {
+    rx_finfo.rxflen() as usize | (rx_finfo.rxfle() as usize) << 7
+}
+
+fn without_fcs(frame: &[u8]) -> &[u8] {
+    &frame[..frame.len().saturating_sub(2)]
+}
+
+
+/// The state of the DW1000's internal state machines
+///
+/// Returned by [`DW1000::sys_state`]. The SYS_STATE register is not documented
+/// in the user manual, so the meaning of the values is based on information
+/// provided by Decawave in their forum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SysState {
+    /// The state of the power management and system control state machine
+    pub pmsc: PmscState,
+
+    /// The raw value of the receive state machine
+    ///
+    /// A value of `0` means the receiver is idle.
+    pub rx: u8,
+
+    /// The raw value of the transmit state machine
+    ///
+    /// A value of `0` means the transmitter is idle.
+    pub tx: u8,
+}
+
+impl SysState {
+    /// Indicates whether the receive state machine is idle
+    pub fn rx_idle(&self) -> bool {
+        self.rx == 0
+    }
+
+    /// Indicates whether the transmit state machine is idle
+    pub fn tx_idle(&self) -> bool {
+        self.tx == 0
+    }
+
+    /// Returns a simplified view of what the transceiver is doing
+    pub fn transceiver_state(&self) -> TransceiverState {
+        match self.pmsc {
+            PmscState::Init | PmscState::Idle     => TransceiverState::Idle,
+            PmscState::TxWait | PmscState::RxWait => TransceiverState::Wait,
+            PmscState::Tx                         => TransceiverState::Tx,
+            PmscState::Rx                         => TransceiverState::Rx,
+            PmscState::Unknown(value)             =>
+                TransceiverState::Unknown(value),
+        }
+    }
+}
+
+
+/// The state of the power management and system control state machine
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PmscState {
+    /// The DW1000 is initializing
+    Init,
+
+    /// The DW1000 is idle
+    Idle,
+
+    /// Waiting for a delayed transmission to start
+    TxWait,
+
+    /// Waiting for a delayed reception to start
+    RxWait,
+
+    /// The DW1000 is transmitting
+    Tx,
+
+    /// The DW1000 is receiving
+    Rx,
+
+    /// A value that isn't documented
+    Unknown(u8),
+}
+
+impl PmscState {
+    fn from_raw(value: u8) -> Self {
+        match value {
+            0x0 => PmscState::Init,
+            0x1 => PmscState::Idle,
+            0x2 => PmscState::TxWait,
+            0x3 => PmscState::RxWait,
+            0x4 => PmscState::Tx,
+            0x5 => PmscState::Rx,
+            _   => PmscState::Unknown(value),
+        }
+    }
+}
+
+
+/// The DW1000's GPIO pins
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GpioPin {
+    /// GPIO0/RXOKLED
+    Gpio0,
+    /// GPIO1/SFDLED
+    Gpio1,
+    /// GPIO2/RXLED
+    Gpio2,
+    /// GPIO3/TXLED
+    Gpio3,
+    /// GPIO4/EXTPA
+    Gpio4,
+    /// GPIO5/EXTTXE
+    Gpio5,
+    /// GPIO6/EXTRXE
+    Gpio6,
+    /// SYNC/GPIO7
+    Gpio7,
+    /// IRQ/GPIO8
+    Gpio8,
+}
+
+
+/// Events that can be routed to a GPIO pin
+///
+/// Used by [`DW1000::route_event_to_gpio`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GpioEvent {
+    /// A frame was received without errors (RXOKLED)
+    RxOk,
+    /// An SFD was detected (SFDLED)
+    Sfd,
+    /// The receiver is enabled (RXLED)
+    Rx,
+    /// A frame is being transmitted (TXLED)
+    Tx,
+    /// Enable signal for an external power amplifier (EXTPA)
+    ExtPa,
+    /// Transmit enable signal for an external switch (EXTTXE)
+    ExtTxEnable,
+    /// Receive enable signal for an external switch (EXTRXE)
+    ExtRxEnable,
+}
+
+impl GpioEvent {
+    /// Returns the pin this event can be routed to
+    pub fn pin(&self) -> GpioPin {
+        match self {
+            GpioEvent::RxOk        => GpioPin::Gpio0,
+            GpioEvent::Sfd         => GpioPin::Gpio1,
+            GpioEvent::Rx          => GpioPin::Gpio2,
+            GpioEvent::Tx          => GpioPin::Gpio3,
+            GpioEvent::ExtPa       => GpioPin::Gpio4,
+            GpioEvent::ExtTxEnable => GpioPin::Gpio5,
+            GpioEvent::ExtRxEnable => GpioPin::Gpio6,
+        }
+    }
+}
+
+
+/// The contents of the PANADR register
+///
+/// Keeps the PAN ID and short address of this node together, so they can't be
+/// swapped accidentally. See [`DW1000::pan_address`] and
+/// [`DW1000::set_pan_address`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PanAddress {
+    /// The PAN ID
+    pub pan_id: mac::PanId,
+
+    /// The short address
+    pub short_addr: mac::ShortAddress,
+}
+
+impl From<PanAddress> for mac::Address {
+    fn from(address: PanAddress) -> Self {
+        mac::Address::Short(address.pan_id, address.short_addr)
+    }
+}
+
+
+/// A snapshot of the node configuration
+///
+/// Returned by [`DW1000::read_config`], and can be restored using
+/// [`DW1000::restore_config`]. Radio settings like channel and bitrate are not
+/// included, as they are passed to [`DW1000::send`] and [`DW1000::receive`]
+/// via [`TxConfig`] and [`RxConfig`] anyway.
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// The PAN ID
+    pub pan_id: mac::PanId,
+
+    /// The short address
+    pub short_address: mac::ShortAddress,
+
+    /// The RX antenna delay
+    pub rx_antenna_delay: u16,
+
+    /// The TX antenna delay
+    pub tx_antenna_delay: u16,
+
+    /// The crystal trim value
+    pub xtal_trim: u8,
+}
+
+
+/// A snapshot of the DW1000's key registers
+///
+/// Returned by [`DW1000::dump_state`]. Registers are printed as raw hexadecimal
+/// values by the `Debug` implementation. Refer to the user manual for their
+/// meaning.
+#[derive(Debug)]
+pub struct StateDump {
+    /// Device identifier
+    pub dev_id: ll::dev_id::R,
+    /// System configuration
+    pub sys_cfg: ll::sys_cfg::R,
+    /// System event status
+    pub sys_status: ll::sys_status::R,
+    /// System state
+    pub sys_state: ll::sys_state::R,
+    /// Channel control
+    pub chan_ctrl: ll::chan_ctrl::R,
+    /// TX frame control
+    pub tx_fctrl: ll::tx_fctrl::R,
+    /// RX frame information
+    pub rx_finfo: ll::rx_finfo::R,
+
+    /// PHR error counter
+    pub evc_phe: u16,
+    /// RSD error counter
+    pub evc_rse: u16,
+    /// Frame check sequence good counter
+    pub evc_fcg: u16,
+    /// Frame check sequence error counter
+    pub evc_fce: u16,
+    /// Frame filter rejection counter
+    pub evc_ffr: u16,
+    /// RX overrun error counter
+    pub evc_ovr: u16,
+    /// SFD timeout counter
+    pub evc_sto: u16,
+    /// Preamble detection timeout counter
+    pub evc_pto: u16,
+    /// RX frame wait timeout counter
+    pub evc_fwto: u16,
+    /// TX frame sent counter
+    pub evc_txfs: u16,
+    /// Half period warning counter
+    pub evc_hpw: u16,
+    /// TX power-up warning counter
+    pub evc_tpw: u16,
+}
+
+
+/// What the transceiver is currently doing
+///
+/// Returned by [`DW1000::transceiver_state`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransceiverState {
+    /// Neither transmitting nor receiving
+    Idle,
+
+    /// Waiting for a delayed transmission or reception to start
+    Wait,
+
+    /// Transmitting
+    Tx,
+
+    /// Receiving
+    Rx,
+
+    /// The PMSC state machine reported an undocumented value
+    Unknown(u8),
+}
+
+
+/// The decoded contents of the TX_FCTRL register
+///
+/// Returned by [`DW1000::read_tx_frame_control`]. Fields that contain a
+/// reserved value are `None`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TxFrameControl {
+    /// The frame length, including the 2-byte FCS (TFLEN, TFLE)
+    pub frame_len: u16,
+
+    /// The bitrate (TXBR)
+    pub bitrate: Option<BitRate>,
+
+    /// Whether the ranging bit is set (TR)
+    pub ranging_enable: bool,
+
+    /// The pulse repetition frequency (TXPRF)
+    pub pulse_repetition_frequency: Option<PulseRepetitionFrequency>,
+
+    /// The preamble length (TXPSR, PE)
+    pub preamble_length: Option<PreambleLength>,
+
+    /// The offset of the frame within the TX buffer (TXBOFFS)
+    pub buffer_offset: u16,
+
+    /// The inter-frame spacing, in preamble symbols (IFSDELAY)
+    pub ifs_delay: u8,
+}
+
+
+/// The frame filtering configuration from SYS_CFG
+///
+/// Returned by [`DW1000::read_frame_filter`]. The frame type flags are only
+/// relevant, if frame filtering is enabled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FrameFilter {
+    /// Whether frame filtering is enabled (FFEN)
+    pub enabled: bool,
+
+    /// Whether the DW1000 behaves as a coordinator (FFBC)
+    pub coordinator: bool,
+
+    /// Whether beacon frames are allowed (FFAB)
+    pub allow_beacon: bool,
+
+    /// Whether data frames are allowed (FFAD)
+    pub allow_data: bool,
+
+    /// Whether acknowledgement frames are allowed (FFAA)
+    pub allow_ack: bool,
+
+    /// Whether MAC command frames are allowed (FFAM)
+    pub allow_mac_command: bool,
+
+    /// Whether reserved frame types are allowed (FFAR)
+    pub allow_reserved: bool,
+
+    /// Whether frames of type 4 are allowed (FFA4)
+    pub allow_type_4: bool,
+
+    /// Whether frames of type 5 are allowed (FFA5)
+    pub allow_type_5: bool,
+}
+
+
+/// The lock status of the DW1000's PLLs
+///
+/// Returned by [`DW1000::pll_status`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PllStatus {
+    /// The clock PLL is locked and hasn't lost lock (CPLOCK, CLKPLL_LL)
+    pub clock_pll_locked: bool,
+
+    /// The RF PLL hasn't lost lock (RFPLL_LL)
+    pub rf_pll_locked: bool,
+}
+
+
+/// Statistics about failed receive operations
+///
+/// Returned by [`DW1000::receive_failure_stats`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReceiveFailureStats {
+    /// Number of SFD timeouts (EVC_STO)
+    pub sfd_timeouts: u16,
+
+    /// Number of preamble detection timeouts (EVC_PTO)
+    pub preamble_timeouts: u16,
+
+    /// Number of frame wait timeouts (EVC_FWTO)
+    pub frame_wait_timeouts: u16,
+
+    /// Number of receiver overruns (EVC_OVR)
+    ///
+    /// An overrun means a frame was lost, because the receiver finished
+    /// receiving another frame before the host had read the previous one. This
+    /// only happens with double buffering enabled. Frequent overruns indicate
+    /// that the host is not reading frames fast enough.
+    pub overruns: u16,
 }
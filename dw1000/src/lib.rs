@@ -15,11 +15,15 @@
 //! microcontroller used on the DWM1001 module), so be aware that you might run
 //! into problems on other devices.
 //!
+//! Support for HALs that only implement `embedded-hal` 1.0 is available via
+//! the [`compat`] module, if the `eh1` feature is enabled.
+//!
 //! [high-level interface]: hl/index.html
 //! [register-level interface]: ll/index.html
 //! [`dwm1001`]: https://crates.io/crates/dwm1001
 //! [usage examples]: https://github.com/braun-robotics/rust-dwm1001/tree/master/examples
 //! [`embedded-hal`]: https://crates.io/crates/embedded-hal
+//! [`compat`]: compat/index.html
 
 
 #![no_std]
@@ -32,6 +36,12 @@ pub mod hl;
 pub mod ranging;
 pub mod time;
 pub mod configs;
+pub mod frame;
+pub mod queue;
+pub mod ring;
+
+#[cfg(feature = "eh1")]
+pub mod compat;
 
 
 #[doc(no_inline)]
@@ -40,13 +50,18 @@ pub use ieee802154::mac;
 pub use crate::hl::{
     DW1000,
     Error,
+    ErrorWithDriver,
     Message,
     Ready,
     Receiving,
+    SendError,
     Sending,
+    Sleeping,
     Uninitialized,
 };
 
+pub use crate::frame::FrameBuilder;
+
 pub use crate::configs::{
     TxConfig,
     RxConfig
@@ -35,6 +35,11 @@ use embedded_hal::{
 pub struct DW1000<SPI, CS> {
     spi        : SPI,
     chip_select: CS,
+
+    max_transfer_len: usize,
+
+    #[cfg(feature = "trace")]
+    trace: Option<&'static mut dyn RegTrace>,
 }
 
 impl<SPI, CS> DW1000<SPI, CS> {
@@ -46,11 +51,74 @@ impl<SPI, CS> DW1000<SPI, CS> {
         DW1000 {
             spi,
             chip_select,
+
+            max_transfer_len: usize::MAX,
+
+            #[cfg(feature = "trace")]
+            trace: None,
+        }
+    }
+
+    /// Sets the maximum length of a single SPI transfer, in bytes
+    ///
+    /// Some HALs limit the length of a single SPI transfer, for example due to
+    /// DMA restrictions. Register accesses that would exceed this limit are
+    /// split into multiple transfers. Each of them starts with its own header,
+    /// addressing the next part of the register via the sub-index, so this
+    /// also works with SPI implementations that manage chip select per
+    /// transfer.
+    ///
+    /// The length includes the header of up to 3 bytes. By default, transfers
+    /// are not limited.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `max_len` is less than 6.
+    pub fn set_max_transfer_len(&mut self, max_len: usize) {
+        assert!(max_len >= 6);
+        self.max_transfer_len = max_len;
+    }
+
+    /// Set the hook that is called on every register access
+    ///
+    /// Only available, if the `trace` feature is enabled.
+    #[cfg(feature = "trace")]
+    pub fn set_trace(&mut self, trace: &'static mut dyn RegTrace) {
+        self.trace = Some(trace);
+    }
+
+    /// Remove the register access hook, if one was set
+    ///
+    /// Only available, if the `trace` feature is enabled.
+    #[cfg(feature = "trace")]
+    pub fn clear_trace(&mut self) {
+        self.trace = None;
+    }
+
+    #[cfg(feature = "trace")]
+    fn trace(&mut self, reg_id: u8, sub_id: u16, is_write: bool, bytes: &[u8]) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.trace(reg_id, sub_id, is_write, bytes);
         }
     }
 }
 
 
+/// Hook for tracing register accesses
+///
+/// If set using [`DW1000::set_trace`], this is called after every register
+/// read and write with the register ID, the sub-register ID, whether the
+/// access was a write, and the register data (without the SPI header).
+///
+/// Only available, if the `trace` feature is enabled. Without it, the tracing
+/// code is not compiled in at all.
+#[cfg(feature = "trace")]
+pub trait RegTrace {
+    /// Called on every register access
+    fn trace(&mut self, reg_id: u8, sub_id: u16, is_write: bool, bytes: &[u8]);
+}
+
+
 /// Provides access to a register
 ///
 /// You can get an instance for a given register using one of the methods on
@@ -69,16 +137,21 @@ impl<'s, R, SPI, CS> RegAccessor<'s, R, SPI, CS>
             R: Register + Readable,
     {
         let mut r      = R::read();
-        let mut buffer = R::buffer(&mut r);
-
-        init_header::<R>(false, &mut buffer);
-
-        self.0.chip_select.set_low()
-            .map_err(|err| Error::ChipSelect(err))?;
-        self.0.spi.transfer(buffer)
-            .map_err(|err| Error::Transfer(err))?;
-        self.0.chip_select.set_high()
-            .map_err(|err| Error::ChipSelect(err))?;
+        let buffer     = R::buffer(&mut r);
+
+        let header_len = init_header(false, R::ID, R::SUB_ID, buffer);
+        self.0.access(false, R::ID, R::SUB_ID, header_len, buffer)?;
+
+        #[cfg(feature = "trace")]
+        {
+            let header_len = R::buffer(&mut r).len() - R::LEN;
+            self.0.trace(
+                R::ID,
+                R::SUB_ID,
+                false,
+                &R::buffer(&mut r)[header_len..],
+            );
+        }
 
         Ok(r)
     }
@@ -94,14 +167,14 @@ impl<'s, R, SPI, CS> RegAccessor<'s, R, SPI, CS>
         f(&mut w);
 
         let buffer = R::buffer(&mut w);
-        init_header::<R>(true, buffer);
+        let header_len = init_header(true, R::ID, R::SUB_ID, buffer);
+        self.0.access(true, R::ID, R::SUB_ID, header_len, buffer)?;
 
-        self.0.chip_select.set_low()
-            .map_err(|err| Error::ChipSelect(err))?;
-        <SPI as spi::Write<u8>>::write(&mut self.0.spi, buffer)
-            .map_err(|err| Error::Write(err))?;
-        self.0.chip_select.set_high()
-            .map_err(|err| Error::ChipSelect(err))?;
+        #[cfg(feature = "trace")]
+        {
+            let header_len = buffer.len() - R::LEN;
+            self.0.trace(R::ID, R::SUB_ID, true, &buffer[header_len..]);
+        }
 
         Ok(())
     }
@@ -123,21 +196,21 @@ impl<'s, R, SPI, CS> RegAccessor<'s, R, SPI, CS>
         f(&mut r, &mut w);
 
         let buffer = <R as Writable>::buffer(&mut w);
-        init_header::<R>(true, buffer);
+        let header_len = init_header(true, R::ID, R::SUB_ID, buffer);
+        self.0.access(true, R::ID, R::SUB_ID, header_len, buffer)?;
 
-        self.0.chip_select.set_low()
-            .map_err(|err| Error::ChipSelect(err))?;
-        <SPI as spi::Write<u8>>::write(&mut self.0.spi, buffer)
-            .map_err(|err| Error::Write(err))?;
-        self.0.chip_select.set_high()
-            .map_err(|err| Error::ChipSelect(err))?;
+        #[cfg(feature = "trace")]
+        {
+            let header_len = buffer.len() - R::LEN;
+            self.0.trace(R::ID, R::SUB_ID, true, &buffer[header_len..]);
+        }
 
         Ok(())
     }
 }
 
 
-/// An SPI error that can occur when communicating with the DW1000
+/// An error that can occur when communicating with the DW1000
 pub enum Error<SPI, CS>
     where
         SPI: spi::Transfer<u8> + spi::Write<u8>,
@@ -151,6 +224,9 @@ pub enum Error<SPI, CS>
 
     /// Error occured while changing chip select signal
     ChipSelect(<CS as OutputPin>::Error),
+
+    /// The access would extend beyond the end of a buffer
+    OutOfBounds,
 }
 
 // We can't derive this implementation, as the compiler will complain that the
@@ -168,39 +244,131 @@ impl<SPI, CS> fmt::Debug for Error<SPI, CS>
             Error::Transfer(error)   => write!(f, "Transfer({:?})", error),
             Error::Write(error)      => write!(f, "Write({:?})", error),
             Error::ChipSelect(error) => write!(f, "ChipSelect({:?})", error),
+            Error::OutOfBounds       => write!(f, "OutOfBounds"),
         }
     }
 }
 
 
+impl<SPI, CS> DW1000<SPI, CS>
+    where
+        SPI: spi::Transfer<u8> + spi::Write<u8>,
+        CS:  OutputPin,
+{
+    /// Transfers a buffer that starts with an initialized header
+    ///
+    /// If the buffer exceeds the maximum transfer length, it is split into
+    /// multiple transfers. The header for each of them is written into the
+    /// buffer right before the respective chunk, and the bytes it replaces are
+    /// restored afterwards.
+    fn access(&mut self,
+        write:      bool,
+        id:         u8,
+        sub_id:     u16,
+        header_len: usize,
+        buffer:     &mut [u8],
+    )
+        -> Result<(), Error<SPI, CS>>
+    {
+        if buffer.len() <= self.max_transfer_len {
+            return self.transfer(write, buffer);
+        }
+
+        // Leave room for the longest possible header.
+        let chunk_len = self.max_transfer_len - 3;
+
+        let mut offset = 0;
+        while header_len + offset < buffer.len() {
+            let start = header_len + offset;
+            let end   = (start + chunk_len).min(buffer.len());
+
+            if offset == 0 {
+                self.transfer(write, &mut buffer[.. end])?;
+            }
+            else {
+                let mut header = [0; 3];
+                let chunk_header_len = init_header(
+                    write,
+                    id,
+                    sub_id + offset as u16,
+                    &mut header,
+                );
+
+                // The chunk length is at least 3, so there's always enough room
+                // for the header before the chunk.
+                let header_start = start - chunk_header_len;
+                let mut saved = [0; 3];
+                saved[.. chunk_header_len]
+                    .copy_from_slice(&buffer[header_start .. start]);
+                buffer[header_start .. start]
+                    .copy_from_slice(&header[.. chunk_header_len]);
+
+                let result = self.transfer(
+                    write,
+                    &mut buffer[header_start .. end],
+                );
+
+                buffer[header_start .. start]
+                    .copy_from_slice(&saved[.. chunk_header_len]);
+                result?;
+            }
+
+            offset += end - start;
+        }
+
+        Ok(())
+    }
+
+    /// Performs a single SPI transfer
+    fn transfer(&mut self, write: bool, buffer: &mut [u8])
+        -> Result<(), Error<SPI, CS>>
+    {
+        self.chip_select.set_low()
+            .map_err(|err| Error::ChipSelect(err))?;
+        if write {
+            <SPI as spi::Write<u8>>::write(&mut self.spi, buffer)
+                .map_err(|err| Error::Write(err))?;
+        }
+        else {
+            self.spi.transfer(buffer)
+                .map_err(|err| Error::Transfer(err))?;
+        }
+        self.chip_select.set_high()
+            .map_err(|err| Error::ChipSelect(err))?;
+
+        Ok(())
+    }
+}
+
+
 /// Initializes the SPI message header
 ///
-/// Initializes the SPI message header for accessing a given register, writing
-/// the header directly into the provided buffer. Returns the length of the
-/// header that was written.
-fn init_header<R: Register>(write: bool, buffer: &mut [u8]) -> usize {
-    let sub_id = R::SUB_ID > 0;
+/// Initializes the SPI message header for accessing a given register and
+/// sub-index, writing the header directly into the provided buffer. Returns
+/// the length of the header that was written.
+fn init_header(write: bool, id: u8, sub_id: u16, buffer: &mut [u8]) -> usize {
+    let has_sub_id = sub_id > 0;
 
     buffer[0] =
-        (((write as u8)  << 7) & 0x80) |
-        (((sub_id as u8) << 6) & 0x40) |
-        (R::ID                 & 0x3f);
+        (((write as u8)      << 7) & 0x80) |
+        (((has_sub_id as u8) << 6) & 0x40) |
+        (id                        & 0x3f);
 
-    if !sub_id {
+    if !has_sub_id {
         return 1;
     }
 
-    let ext_addr = R::SUB_ID > 127;
+    let ext_addr = sub_id > 127;
 
     buffer[1] =
         (((ext_addr as u8) << 7) & 0x80) |
-        (R::SUB_ID as u8         & 0x7f); // lower 7 bits (of 15)
+        (sub_id as u8            & 0x7f); // lower 7 bits (of 15)
 
     if !ext_addr {
         return 2;
     }
 
-    buffer[2] = ((R::SUB_ID & 0x7f80) >> 7) as u8; // higher 8 bits (of 15)
+    buffer[2] = ((sub_id & 0x7f80) >> 7) as u8; // higher 8 bits (of 15)
 
     3
 }
@@ -545,6 +713,39 @@ macro_rules! impl_register {
                         }
                     )*
                 }
+
+
+                /// Checks that every field reads back what was written to it
+                #[cfg(test)]
+                mod round_trip {
+                    use super::{
+                        HEADER_LEN,
+                        R,
+                        W,
+                    };
+
+                    $(
+                        #[test]
+                        fn $field() {
+                            fn prop(value: u64) -> bool {
+                                const SIZE_IN_BITS: usize =
+                                    $last_bit - $first_bit + 1;
+
+                                // Random values are masked to the field width.
+                                let value = (value
+                                    & (u64::MAX >> (64 - SIZE_IN_BITS)))
+                                    as $ty;
+
+                                let mut w = W([0; HEADER_LEN + $len]);
+                                w.$field(value);
+
+                                R(w.0).$field() == value
+                            }
+
+                            quickcheck::quickcheck(prop as fn(u64) -> bool);
+                        }
+                    )*
+                }
             }
 
             impl_rw!($rw, $name, $name_lower, $len);
@@ -743,6 +944,24 @@ impl_register! {
         rxprej,    33, 33, u8; /// RX Preamble Rejection
         txpute,    34, 34, u8; /// TX Power Up Time Error
     }
+    0x0F, 0x00, 2, RO, SYS_STATUS_LO(sys_status_lo) { /// Low 2 bytes of SYS_STATUS, for faster polling
+        irqs,       0,  0, u8; /// Interrupt Request Status
+        cplock,     1,  1, u8; /// Clock PLL Lock
+        esyncr,     2,  2, u8; /// External Sync Clock Reset
+        aat,        3,  3, u8; /// Automatic Acknowledge Trigger
+        txfrb,      4,  4, u8; /// TX Frame Begins
+        txprs,      5,  5, u8; /// TX Preamble Sent
+        txphs,      6,  6, u8; /// TX PHY Header Sent
+        txfrs,      7,  7, u8; /// TX Frame Sent
+        rxprd,      8,  8, u8; /// RX Preamble Detected
+        rxsfdd,     9,  9, u8; /// RX SFD Detected
+        ldedone,   10, 10, u8; /// LDE Processing Done
+        rxphd,     11, 11, u8; /// RX PHY Header Detect
+        rxphe,     12, 12, u8; /// RX PHY Header Error
+        rxdfr,     13, 13, u8; /// RX Data Frame Ready
+        rxfcg,     14, 14, u8; /// RX FCS Good
+        rxfce,     15, 15, u8; /// RX FCS Error
+    }
     0x10, 0x00, 4, RO, RX_FINFO(rx_finfo) { /// RX Frame Information
         rxflen,  0,  6, u8; /// Receive Frame Length
         rxfle,   7,  9, u8; /// Receive Frame Length Extension
@@ -751,6 +970,13 @@ impl_register! {
         rng,    15, 15, u8; /// Receiver Ranging
         rxprfr, 16, 17, u8; /// RX Pulse Repetition Rate Report
         rxpsr,  18, 19, u8; /// RX Preamble Repetition
+        rxpacc, 20, 31, u16; /// Preamble Accumulation Count
+    }
+    0x12, 0x00, 8, RO, RX_FQUAL(rx_fqual) { /// Rx Frame Quality Information
+        std_noise,  0, 15, u16; /// Standard Deviation of Noise
+        fp_ampl2,  16, 31, u16; /// First Path Amplitude point 2
+        fp_ampl3,  32, 47, u16; /// First Path Amplitude point 3
+        cir_pwr,   48, 63, u16; /// Channel Impulse Response Power
     }
     0x15, 0x00, 14, RO, RX_TIME(rx_time) { /// Receive Time Stamp
         rx_stamp,  0,  39, u64; /// Fully adjusted time stamp
@@ -770,12 +996,22 @@ impl_register! {
         rx_state,    8, 12, u8; /// Current Receive State Machine value
         pmsc_state, 16, 23, u8; /// Current PMSC State Machine value
     }
+    0x1D, 0x00, 4, RW, RX_SNIFF(rx_sniff) { /// Sniff Mode Configuration
+        sniff_ont,   0,  3, u8; /// SNIFF Mode ON time, in PACs
+        sniff_offt,  8, 15, u8; /// SNIFF Mode OFF time, in microseconds
+    }
     0x1E, 0x00, 4, RW, TX_POWER(tx_power) { /// TX Power Control
         // The TX_POWER register has multiple sets of fields defined, depending
-        // on the smart TX power control setting. I don't know how to model
-        // this, so I've opted to provide just a single `value` field for
-        // maximum flexibility.
-        value, 0, 31, u32; /// TX Power Control value
+        // on the smart TX power control setting. All of them are provided
+        // here, as overlapping fields. Which ones apply depends on DIS_STXP in
+        // SYS_CFG. The `value` field covers the whole register.
+        value,      0, 31, u32; /// TX Power Control value
+        boostnorm,  0,  7, u8;  /// Normal power setting (smart TX power)
+        boostp500,  8, 15, u8;  /// Power for frames of 0.5 ms or shorter (smart TX power)
+        boostp250, 16, 23, u8;  /// Power for frames of 0.25 ms or shorter (smart TX power)
+        boostp125, 24, 31, u8;  /// Power for frames of 0.125 ms or shorter (smart TX power)
+        txpowphr,   8, 15, u8;  /// Power for the SHR and PHR portion of the frame (manual TX power)
+        txpowsd,   16, 23, u8;  /// Power for the data portion of the frame (manual TX power)
     }
     0x1F, 0x00, 4, RW, CHAN_CTRL(chan_ctrl) { /// Channel Control Register
         tx_chan, 0, 3, u8; /// Selects the transmit channel.
@@ -966,9 +1202,22 @@ impl_register! {
         txmq,    9, 11, u8; /// Transmit mixer Q-factor tuning register
         value, 0, 23, u32; /// The entire register
     }
+    0x28, 0x11, 1, RW, RF_SENSOR_BIAS(rf_sensor_bias) { /// Temperature and voltage sensor bias (undocumented, see user manual 6.4)
+        value, 0, 7, u8; /// value
+    }
+    0x28, 0x12, 1, RW, RF_SENSOR_CTRL(rf_sensor_ctrl) { /// Temperature and voltage sensor control (undocumented, see user manual 6.4)
+        value, 0, 7, u8; /// value
+    }
     0x28, 0x30, 5, RW, LDOTUNE(ldotune) { /// LDO voltage tuning parameter
         value, 0, 39, u64; /// Internal LDO voltage tuning parameter
     }
+    0x2A, 0x00, 2, RW, TC_SARC(tc_sarc) { /// Transmitter Calibration - SAR control
+        sar_ctrl, 0, 0, u8; /// Start an ADC conversion
+    }
+    0x2A, 0x03, 3, RO, TC_SARL(tc_sarl) { /// Transmitter Calibration - Latest SAR readings
+        sar_lvbat,  0,  7, u8; /// Latest SAR reading for voltage level
+        sar_ltemp,  8, 15, u8; /// Latest SAR reading for temperature level
+    }
     0x2A, 0x0B, 1, RW, TC_PGDELAY(tc_pgdelay) { /// Pulse Generator Delay
         value, 0, 7, u8; /// Transmitter Calibration - Pulse Generator Delay
     }
@@ -978,6 +1227,47 @@ impl_register! {
     0x2B, 0x0B, 1, RW, FS_PLLTUNE(fs_plltune) { /// Frequency synth - PLL Tuning
         value, 0, 7, u8; /// Frequency synthesiser - PLL Tuning
     }
+    0x2B, 0x0E, 1, RW, FS_XTALT(fs_xtalt) { /// Frequency synth - Crystal trim
+        xtalt,     0, 4, u8; /// Crystal Trim
+        reserved,  5, 7, u8; /// Must always be set to binary 011
+    }
+    0x2C, 0x00, 2, RW, AON_WCFG(aon_wcfg) { /// AON Wakeup Configuration
+        onw_radc,    0,  0, u8; /// On Wake-up Run the (temperature and voltage) ADC
+        onw_rx,      1,  1, u8; /// On Wake-up turn on the Receiver
+        onw_leui,    3,  3, u8; /// On Wake-up load the EUI from OTP memory
+        onw_ldc,     6,  6, u8; /// On Wake-up load configurations from the AON memory
+        onw_l64p,    7,  7, u8; /// On Wake-up load the Length64 receiver operating parameter set
+        pres_sleep,  8,  8, u8; /// Preserve Sleep
+        onw_llde,   11, 11, u8; /// On Wake-up load the LDE microcode
+        onw_lldo,   12, 12, u8; /// On Wake-up load the LDOTUNE value from OTP
+    }
+    0x2C, 0x02, 1, RW, AON_CTRL(aon_ctrl) { /// AON Control Register
+        restore,  0, 0, u8; /// Copy the user configurations from the AON memory to the host interface register set
+        save,     1, 1, u8; /// Copy the user configurations from the host interface register set into the AON memory
+        upl_cfg,  2, 2, u8; /// Upload the AON block configurations to the AON
+        dca_read, 3, 3, u8; /// Direct AON memory access read
+        dca_enab, 7, 7, u8; /// Direct AON memory access enable bit
+    }
+    0x2C, 0x03, 1, RO, AON_RDAT(aon_rdat) { /// AON Direct Access Read Data Result
+        value, 0, 7, u8; /// AON Direct Access Read Data Result
+    }
+    0x2C, 0x04, 1, RW, AON_ADDR(aon_addr) { /// AON Direct Access Address
+        value, 0, 7, u8; /// AON Direct Access Address
+    }
+    0x2C, 0x06, 4, RW, AON_CFG0(aon_cfg0) { /// AON Configuration Register 0
+        sleep_en,   0,  0, u8;  /// Sleep enable configuration bit
+        wake_pin,   1,  1, u8;  /// Wake using WAKEUP pin
+        wake_spi,   2,  2, u8;  /// Wake using SPI access
+        wake_cnt,   3,  3, u8;  /// Wake when sleep counter elapses
+        lpdiv_en,   4,  4, u8;  /// Low power divider enable configuration
+        lpclkdiva,  5, 15, u16; /// Divider count for dividing the raw DW1000 XTAL oscillator frequency
+        sleep_tim, 16, 31, u16; /// Sleep time
+    }
+    0x2C, 0x0A, 2, RW, AON_CFG1(aon_cfg1) { /// AON Configuration Register 1
+        sleep_ce, 0, 0, u8; /// Sleep counter enable
+        smxx,     1, 1, u8; /// Enable the SLEEP/DEEPSLEEP modes
+        lposc_c,  2, 2, u8; /// Low power oscillator calibration enable
+    }
     0x2D, 0x04, 2, RW, OTP_ADDR(otp_addr) { /// OTP Address
         value, 0, 10, u16; /// OTP Address
     }
@@ -1006,6 +1296,36 @@ impl_register! {
         evc_en,  0, 0, u8; /// Event Counters Enable
         evc_clr, 1, 1, u8; /// Event Counters Clear
     }
+    0x2F, 0x04, 2, RO, EVC_PHE(evc_phe) { /// PHR Error Counter
+        value, 0, 11, u16; /// PHR Error Event Counter
+    }
+    0x2F, 0x06, 2, RO, EVC_RSE(evc_rse) { /// RSD Error Counter
+        value, 0, 11, u16; /// Reed Solomon decoder (Frame Sync Loss) Error Event Counter
+    }
+    0x2F, 0x08, 2, RO, EVC_FCG(evc_fcg) { /// Frame Check Sequence Good Counter
+        value, 0, 11, u16; /// Frame Check Sequence Good Event Counter
+    }
+    0x2F, 0x0A, 2, RO, EVC_FCE(evc_fce) { /// Frame Check Sequence Error Counter
+        value, 0, 11, u16; /// Frame Check Sequence Error Event Counter
+    }
+    0x2F, 0x0C, 2, RO, EVC_FFR(evc_ffr) { /// Frame Filter Rejection Counter
+        value, 0, 11, u16; /// Frame Filter Rejection Event Counter
+    }
+    0x2F, 0x0E, 2, RO, EVC_OVR(evc_ovr) { /// RX Overrun Error Counter
+        value, 0, 11, u16; /// RX Overrun Error Event Counter
+    }
+    0x2F, 0x10, 2, RO, EVC_STO(evc_sto) { /// SFD Timeout Counter
+        value, 0, 11, u16; /// SFD timeout Error Event Counter
+    }
+    0x2F, 0x12, 2, RO, EVC_PTO(evc_pto) { /// Preamble Detection Timeout Counter
+        value, 0, 11, u16; /// Preamble Detection Timeout Event Counter
+    }
+    0x2F, 0x14, 2, RO, EVC_FWTO(evc_fwto) { /// RX Frame Wait Timeout Counter
+        value, 0, 11, u16; /// RX Frame Wait Timeout Event Counter
+    }
+    0x2F, 0x16, 2, RO, EVC_TXFS(evc_txfs) { /// TX Frame Sent Counter
+        value, 0, 11, u16; /// TX Frame Sent Event Counter
+    }
     0x2F, 0x18, 2, RO, EVC_HPW(evc_hpw) { /// Half Period Warning Counter
         value, 0, 11, u16; /// Half Period Warning Event Counter
     }
@@ -1023,8 +1343,9 @@ impl_register! {
         gprn,      17, 17, u8; /// GPIO Reset (Not), active low
         gpdce,     18, 18, u8; /// GPIO De-bounce Clock Enable
         gpdrn,     19, 19, u8; /// GPIO De-bounce Reset (Not), active low
-        khzclken,  23, 23, u8; /// Kilohertz Clock Enable
-        softreset, 28, 31, u8; /// Soft Reset
+        khzclken,    23, 23, u8; /// Kilohertz Clock Enable
+        pll2_seq_en, 24, 24, u8; /// Enable PLL2 on/off sequencing by SNIFF mode
+        softreset,   28, 31, u8; /// Soft Reset
     }
     0x36, 0x04, 4, RW, PMSC_CTRL1(pmsc_ctrl1) { /// PMSC Control Register 1
         arx2init,   1,  1, u8; /// Automatic transition from receive to init
@@ -1037,6 +1358,12 @@ impl_register! {
         lderune,   17, 17, u8; /// LDE Run Enable
         khzclkdiv, 26, 31, u8; /// Kilohertz Clock Divisor
     }
+    0x36, 0x0C, 1, RW, PMSC_SNOZT(pmsc_snozt) { /// PMSC Snooze Time Register
+        snoz_tim, 0, 7, u8; /// Snooze Time Period
+    }
+    0x36, 0x26, 2, RW, PMSC_TXFSEQ(pmsc_txfseq) { /// PMSC fine grain TX sequencing control
+        txfineseq, 0, 15, u16; /// Fine grain TX sequencing control
+    }
     0x36, 0x28, 4, RW, PMSC_LEDC(pmsc_ledc) { /// PMSC LED Control Register
         blink_tim, 0, 7, u8; /// Blink time count value
         blnken, 8, 8, u8; /// Blink Enable
@@ -1077,6 +1404,68 @@ impl<SPI, CS> DW1000<SPI, CS> {
     }
 }
 
+impl<SPI, CS> DW1000<SPI, CS>
+    where
+        SPI: spi::Transfer<u8> + spi::Write<u8>,
+        CS:  OutputPin,
+{
+    /// Write to the transmit data buffer, starting at the given offset
+    ///
+    /// Unlike [`DW1000::tx_buffer`], which always writes the buffer starting
+    /// at index 0 and only covers the first 127 bytes, this writes `data` to
+    /// the region starting at `offset`, anywhere in the 1024-byte buffer. This
+    /// can be used to update parts of a frame (for example the payload)
+    /// without rewriting the rest, or to prepare frames in different regions
+    /// of the buffer.
+    ///
+    /// Returns [`Error::OutOfBounds`], if the write would extend beyond the
+    /// end of the buffer. Nothing is written in that case.
+    pub fn write_tx_buffer_at(&mut self, offset: u16, data: &[u8])
+        -> Result<(), Error<SPI, CS>>
+    {
+        if offset as usize + data.len() > TX_BUFFER_CAPACITY {
+            return Err(Error::OutOfBounds);
+        }
+
+        // Write in chunks of the size of `TX_BUFFER`, so we don't need a large
+        // buffer on the stack. The offset is used as the sub-index. See user
+        // manual, section 2.2.1.2. Header and data of each chunk are sent in a
+        // single write, so this also works with SPI implementations that
+        // manage chip select per write.
+        let mut done = 0;
+        while done < data.len() {
+            let chunk_len = (data.len() - done).min(TX_BUFFER::LEN);
+            let sub_id    = offset + done as u16;
+            let chunk     = &data[done .. done + chunk_len];
+
+            let mut buffer = [0; 3 + TX_BUFFER::LEN];
+            let header_len =
+                init_header(true, TX_BUFFER::ID, sub_id, &mut buffer);
+            buffer[header_len .. header_len + chunk_len]
+                .copy_from_slice(chunk);
+
+            self.access(
+                true,
+                TX_BUFFER::ID,
+                sub_id,
+                header_len,
+                &mut buffer[.. header_len + chunk_len],
+            )?;
+
+            #[cfg(feature = "trace")]
+            self.trace(TX_BUFFER::ID, sub_id, true, chunk);
+
+            done += chunk_len;
+        }
+
+        Ok(())
+    }
+}
+
+
+/// The size of the transmit data buffer in bytes
+pub const TX_BUFFER_CAPACITY: usize = 1024;
+
 
 /// Transmit Data Buffer
 pub mod tx_buffer {
@@ -1125,6 +1514,55 @@ impl<SPI, CS> DW1000<SPI, CS> {
 }
 
 
+impl<SPI, CS> DW1000<SPI, CS>
+    where
+        SPI: spi::Transfer<u8> + spi::Write<u8>,
+        CS:  OutputPin,
+{
+    /// Read from the receive data buffer, starting at the given offset
+    ///
+    /// Unlike [`DW1000::rx_buffer`], which only covers the first 127 bytes,
+    /// this can read from anywhere in the 1024-byte buffer. This is required
+    /// for frames longer than 127 bytes, which can be received in extended PHR
+    /// mode.
+    ///
+    /// Reads that would extend beyond the end of the buffer are silently
+    /// truncated.
+    pub fn read_rx_buffer_at(&mut self, offset: u16, data: &mut [u8])
+        -> Result<(), Error<SPI, CS>>
+    {
+        const BUFFER_LEN: usize = 1024;
+
+        let offset = (offset as usize).min(BUFFER_LEN);
+        let len    = data.len().min(BUFFER_LEN - offset);
+
+        // Read in chunks of the size of `RX_BUFFER`, so we don't need a large
+        // buffer on the stack. The offset is used as the sub-index.
+        let mut done = 0;
+        while done < len {
+            let chunk_len = (len - done).min(RX_BUFFER::LEN);
+            let sub_id    = (offset + done) as u16;
+
+            let mut buffer = [0; 3 + RX_BUFFER::LEN];
+            let header_len =
+                init_header(false, RX_BUFFER::ID, sub_id, &mut buffer);
+            let buffer = &mut buffer[.. header_len + chunk_len];
+
+            self.access(false, RX_BUFFER::ID, sub_id, header_len, buffer)?;
+
+            #[cfg(feature = "trace")]
+            self.trace(RX_BUFFER::ID, sub_id, false, &buffer[header_len..]);
+
+            data[done .. done + chunk_len]
+                .copy_from_slice(&buffer[header_len..]);
+            done += chunk_len;
+        }
+
+        Ok(())
+    }
+}
+
+
 /// Receive Data Buffer
 pub mod rx_buffer {
     use core::fmt;
@@ -0,0 +1,255 @@
+//! A fixed-capacity queue for sending frames back-to-back
+//!
+//! [`TxQueue`] holds frames that are waiting to be sent, and sends them one
+//! after the other using [`DW1000::send`]. It doesn't allocate. Instead, the
+//! storage for the queued frames is provided by the user.
+//!
+//! Frames are added using [`TxQueue::enqueue`]. [`TxQueue::pump`] starts
+//! sending the frame at the front of the queue. Once [`DW1000::wait`] reports
+//! that the transmitter is free again, [`TxQueue::finish`] removes the frame
+//! from the queue and returns the driver, so the next frame can be pumped.
+//! None of these methods block, so this works from an interrupt handler.
+//!
+//! The queue doesn't wait for acknowledgements. A frame counts as sent, once
+//! the DW1000 has transmitted it, whether or not it is received. If frames
+//! need to be acknowledged, receive the ACK after each call to
+//! [`TxQueue::finish`], and enqueue the frame again if none arrives in time.
+//! The sequence number returned by [`TxQueue::pump`] can be used to match the
+//! ACK to its frame.
+//!
+//! # Example
+//!
+//! ``` rust
+//! use dw1000::queue::{
+//!     QueueError,
+//!     QueuedFrame,
+//!     TxQueue,
+//! };
+//!
+//! let mut storage = [QueuedFrame::empty(); 2];
+//! let mut queue   = TxQueue::new(&mut storage);
+//!
+//! queue.enqueue(b"first", None).unwrap();
+//! queue.enqueue(b"second", None).unwrap();
+//! assert_eq!(queue.enqueue(b"third", None), Err(QueueError::Full));
+//! assert_eq!(queue.len(), 2);
+//! ```
+//!
+//! [`DW1000::send`]: ../hl/struct.DW1000.html#method.send
+//! [`DW1000::wait`]: ../hl/struct.DW1000.html#method.wait
+
+
+use embedded_hal::{
+    blocking::spi,
+    digital::v2::OutputPin,
+};
+
+use crate::{
+    mac,
+    Error,
+    ErrorWithDriver,
+    Ready,
+    Sending,
+    TxConfig,
+    DW1000,
+};
+
+
+/// The maximum length of the data in a queued frame
+///
+/// This is what's left of a 127-byte frame, after the longest MAC header that
+/// [`DW1000::send`] creates (17 bytes, for an extended destination address)
+/// and the 2-byte FCS.
+///
+/// [`DW1000::send`]: ../hl/struct.DW1000.html#method.send
+pub const MAX_DATA_LEN: usize = 127 - 17 - 2;
+
+
+/// A fixed-capacity queue of frames waiting to be sent
+///
+/// See [module documentation](index.html).
+pub struct TxQueue<'s> {
+    slots: &'s mut [QueuedFrame],
+    head:  usize,
+    len:   usize,
+}
+
+impl<'s> TxQueue<'s> {
+    /// Creates a new, empty queue
+    ///
+    /// The capacity of the queue is the length of `slots`.
+    pub fn new(slots: &'s mut [QueuedFrame]) -> Self {
+        TxQueue {
+            slots,
+            head: 0,
+            len:  0,
+        }
+    }
+
+    /// Returns the number of frames in the queue
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Indicates whether the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Indicates whether the queue is full
+    pub fn is_full(&self) -> bool {
+        self.len == self.slots.len()
+    }
+
+    /// Adds a frame to the end of the queue
+    ///
+    /// `data` and `destination` have the same meaning as the respective
+    /// arguments of [`DW1000::send`]. As with that method, the data and the
+    /// MAC header must fit into a 127-byte frame together.
+    ///
+    /// Returns [`QueueError::TooLong`], if `data` is longer than
+    /// [`MAX_DATA_LEN`], and [`QueueError::Full`], if the queue is full.
+    ///
+    /// [`DW1000::send`]: ../hl/struct.DW1000.html#method.send
+    pub fn enqueue(&mut self,
+        data:        &[u8],
+        destination: Option<mac::Address>,
+    )
+        -> Result<(), QueueError>
+    {
+        if data.len() > MAX_DATA_LEN {
+            return Err(QueueError::TooLong);
+        }
+        if self.is_full() {
+            return Err(QueueError::Full);
+        }
+
+        let index = (self.head + self.len) % self.slots.len();
+        let slot  = &mut self.slots[index];
+
+        slot.data[..data.len()].copy_from_slice(data);
+        slot.len         = data.len();
+        slot.destination = destination;
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Removes all frames from the queue
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len  = 0;
+    }
+
+    /// Starts sending the next frame in the queue
+    ///
+    /// Sends the frame at the front of the queue using [`DW1000::send`], and
+    /// returns [`Pump::Sending`], with the `DW1000` instance in the `Sending`
+    /// state and the sequence number the frame is sent with. The sequence
+    /// number can be used to match acknowledgements to queued frames. If the
+    /// queue is empty, the instance is returned unchanged in [`Pump::Empty`].
+    ///
+    /// The frame stays in the queue, until it is removed by
+    /// [`TxQueue::finish`]. If an error occurs, the `DW1000` instance is
+    /// consumed, as with [`DW1000::send`].
+    ///
+    /// [`DW1000::send`]: ../hl/struct.DW1000.html#method.send
+    pub fn pump<SPI, CS>(&mut self,
+        dw1000: DW1000<SPI, CS, Ready>,
+        config: TxConfig,
+    )
+        -> Result<Pump<SPI, CS>, Error<SPI, CS>>
+        where
+            SPI: spi::Transfer<u8> + spi::Write<u8>,
+            CS:  OutputPin,
+    {
+        if self.is_empty() {
+            return Ok(Pump::Empty(dw1000));
+        }
+
+        let seq  = dw1000.next_seq();
+        let slot = &self.slots[self.head];
+
+        let sending = dw1000.send(
+            &slot.data[..slot.len],
+            slot.destination,
+            None,
+            config,
+        )?;
+
+        Ok(Pump::Sending(sending, seq))
+    }
+
+    /// Finishes sending a frame started by [`TxQueue::pump`]
+    ///
+    /// Should be called once [`DW1000::wait`] has returned successfully. The
+    /// sent frame is then removed from the queue, and the `DW1000` instance is
+    /// returned in the `Ready` state, so the next frame can be pumped.
+    ///
+    /// If the frame hasn't been sent completely, the transmission is aborted
+    /// (see [`DW1000::finish_sending`]), and the frame stays in the queue, so
+    /// it can be sent again. If an error occurs, the `DW1000` instance is
+    /// returned in the `Sending` state along with it.
+    ///
+    /// [`DW1000::wait`]: ../hl/struct.DW1000.html#method.wait
+    /// [`DW1000::finish_sending`]: ../hl/struct.DW1000.html#method.finish_sending
+    pub fn finish<SPI, CS>(&mut self, sending: DW1000<SPI, CS, Sending>)
+        -> Result<DW1000<SPI, CS, Ready>, ErrorWithDriver<SPI, CS, Sending>>
+        where
+            SPI: spi::Transfer<u8> + spi::Write<u8>,
+            CS:  OutputPin,
+    {
+        let sent   = sending.is_finished();
+        let dw1000 = sending.finish_sending()?;
+
+        if sent && !self.is_empty() {
+            self.head  = (self.head + 1) % self.slots.len();
+            self.len  -= 1;
+        }
+
+        Ok(dw1000)
+    }
+}
+
+
+/// The result of [`TxQueue::pump`]
+#[derive(Debug)]
+pub enum Pump<SPI, CS> {
+    /// The queue was empty, and nothing was sent
+    Empty(DW1000<SPI, CS, Ready>),
+
+    /// The next frame is being sent with the given sequence number
+    Sending(DW1000<SPI, CS, Sending>, u8),
+}
+
+
+/// Storage for a frame in a [`TxQueue`]
+#[derive(Clone, Copy)]
+pub struct QueuedFrame {
+    data:        [u8; MAX_DATA_LEN],
+    len:         usize,
+    destination: Option<mac::Address>,
+}
+
+impl QueuedFrame {
+    /// Creates empty storage for a queued frame
+    pub const fn empty() -> Self {
+        QueuedFrame {
+            data:        [0; MAX_DATA_LEN],
+            len:         0,
+            destination: None,
+        }
+    }
+}
+
+
+/// An error that can occur when adding a frame to a [`TxQueue`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QueueError {
+    /// The queue is full
+    Full,
+
+    /// The data is longer than [`MAX_DATA_LEN`]
+    TooLong,
+}
@@ -165,12 +165,31 @@ impl<T> TxMessage<T> where T: Message {
     /// Send this message via the DW1000
     ///
     /// Serializes the message payload and uses [`DW1000::send`] internally to
-    /// send it.
+    /// send it, using the default [`TxConfig`].
     pub fn send<'r, SPI, CS>(&self, dw1000: DW1000<SPI, CS, Ready>)
         -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
         where
             SPI: spi::Transfer<u8> + spi::Write<u8>,
             CS:  OutputPin,
+    {
+        self.send_with_config(dw1000, TxConfig::default())
+    }
+
+    /// Send this message via the DW1000, using the provided configuration
+    ///
+    /// Works like [`TxMessage::send`], but allows overriding the bit rate,
+    /// preamble length, and other transmit parameters for this message only.
+    /// This is useful for protocols that send different message types with
+    /// different settings, for example a shorter preamble for the final
+    /// message.
+    pub fn send_with_config<SPI, CS>(&self,
+        dw1000: DW1000<SPI, CS, Ready>,
+        config: TxConfig,
+    )
+        -> Result<DW1000<SPI, CS, Sending>, Error<SPI, CS>>
+        where
+            SPI: spi::Transfer<u8> + spi::Write<u8>,
+            CS:  OutputPin,
     {
         // Create a buffer that fits the biggest message currently implemented.
         // This is a really ugly hack. The size of the buffer should just be
@@ -190,7 +209,7 @@ impl<T> TxMessage<T> where T: Message {
             &buf[..T::LEN],
             self.recipient,
             Some(self.tx_time),
-            TxConfig::default()
+            config,
         )?;
 
         Ok(future)
@@ -436,3 +455,202 @@ pub enum ComputeDistanceError {
     /// The time of flight is so large, the distance calculation would overflow
     TimeOfFlightTooLarge,
 }
+
+
+/// The result of a single-sided two-way ranging exchange
+///
+/// Returned by [`compute_single_sided`]. Contains the raw durations the
+/// distance was computed from, so you can apply your own bias correction.
+#[derive(Clone, Copy, Debug)]
+pub struct TwoWayResult {
+    /// The time between sending the poll and receiving the response
+    pub round_trip: Duration,
+
+    /// The time the responder took between receiving the poll and sending the
+    /// response
+    pub reply: Duration,
+
+    /// The computed distance in meters
+    ///
+    /// Requires the `float` feature.
+    #[cfg(feature = "float")]
+    pub distance: f32,
+
+    /// The computed distance in millimeters
+    pub distance_mm: u64,
+}
+
+
+/// Computes the result of a single-sided two-way ranging exchange
+///
+/// Single-sided two-way ranging is described in the DW1000 user manual,
+/// section 12.3.1. `poll_tx_time` and `response_rx_time` are in local time,
+/// `reply` is the responder's reply time, as measured by the responder. The
+/// distance is based on the speed of light in air ([`SPEED_OF_LIGHT_AIR`]).
+///
+/// The round-trip time takes wrap-around of the 40-bit timestamps into
+/// account. If the reply time is larger than the round-trip time, the
+/// distance is 0.
+pub fn compute_single_sided(
+    poll_tx_time:     Instant,
+    response_rx_time: Instant,
+    reply:            Duration,
+)
+    -> TwoWayResult
+{
+    let round_trip = response_rx_time.duration_since(poll_tx_time);
+
+    let time_of_flight = round_trip.value().saturating_sub(reply.value()) / 2;
+
+    // `time_of_flight` is at most half of `round_trip`, so the following will
+    // never panic.
+    let time_of_flight = Duration::new(time_of_flight).unwrap();
+
+    TwoWayResult {
+        round_trip,
+        reply,
+        #[cfg(feature = "float")]
+        distance: time_of_flight_to_distance(
+            time_of_flight,
+            SPEED_OF_LIGHT_AIR,
+        ),
+        distance_mm: time_of_flight_to_distance_mm(
+            time_of_flight,
+            SPEED_OF_LIGHT_AIR_M_PER_S,
+        ),
+    }
+}
+
+
+/// The speed of light in air, in m/s
+///
+/// See [`SPEED_OF_LIGHT_AIR_M_PER_S`] for an integer variant.
+pub const SPEED_OF_LIGHT_AIR: f64 = 299_702_547.0;
+
+/// The speed of light in air, in m/s, as an integer
+pub const SPEED_OF_LIGHT_AIR_M_PER_S: u64 = 299_702_547;
+
+
+/// Converts a time of flight into a distance in meters
+///
+/// `speed_m_per_s` is the propagation speed of the signal. Use
+/// [`SPEED_OF_LIGHT_AIR`] for over-the-air measurements, or the speed of light
+/// multiplied by the velocity factor of the cable, when testing with cables.
+///
+/// Requires the `float` feature. See [`time_of_flight_to_distance_mm`] for a
+/// variant that doesn't use floating-point math.
+#[cfg(feature = "float")]
+pub fn time_of_flight_to_distance(tof: Duration, speed_m_per_s: f64) -> f32 {
+    // One time unit of the DW1000 is 1 / (128 * 499.2 MHz), roughly 15.65 ps.
+    const TIME_UNIT_S: f64 = 1.0 / (128.0 * 499.2e6);
+
+    (tof.value() as f64 * TIME_UNIT_S * speed_m_per_s) as f32
+}
+
+
+/// Converts a time of flight into a distance in millimeters
+///
+/// Works like [`time_of_flight_to_distance`], but only uses integer math,
+/// which makes it suitable for targets without an FPU. Use
+/// [`SPEED_OF_LIGHT_AIR_M_PER_S`] for over-the-air measurements. The result
+/// is rounded down.
+pub fn time_of_flight_to_distance_mm(tof: Duration, speed_m_per_s: u64)
+    -> u64
+{
+    // One time unit of the DW1000 is 1 / (128 * 499.2 MHz), roughly 15.65 ps.
+    const TIME_UNITS_PER_S: u128 = 128 * 499_200_000;
+
+    // A 40-bit duration multiplied by a speed below 2^32 m/s and by 1000 fits
+    // comfortably into 128 bits.
+    let distance_mm = tof.value() as u128 * speed_m_per_s as u128 * 1000
+        / TIME_UNITS_PER_S;
+
+    distance_mm as u64
+}
+
+
+/// A moving average filter for range measurements
+///
+/// Single range measurements are noisy. This filter smoothes them by averaging
+/// the last `N` measurements. It doesn't require any heap allocation.
+///
+/// Requires the `float` feature.
+///
+/// # Example
+///
+/// ``` rust
+/// use dw1000::ranging::RangeFilter;
+///
+/// let mut filter = RangeFilter::<4>::new();
+/// assert_eq!(filter.estimate(), None);
+///
+/// filter.push(1.0);
+/// filter.push(3.0);
+/// assert_eq!(filter.estimate(), Some(2.0));
+///
+/// // Only the last 4 measurements are taken into account.
+/// for _ in 0 .. 4 {
+///     filter.push(5.0);
+/// }
+/// assert_eq!(filter.estimate(), Some(5.0));
+/// ```
+#[cfg(feature = "float")]
+#[derive(Clone, Debug)]
+pub struct RangeFilter<const N: usize> {
+    samples: [f32; N],
+    next:    usize,
+    len:     usize,
+}
+
+#[cfg(feature = "float")]
+impl<const N: usize> RangeFilter<N> {
+    /// Creates a new, empty filter
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `N` is 0.
+    pub fn new() -> Self {
+        assert!(N > 0);
+
+        RangeFilter {
+            samples: [0.0; N],
+            next:    0,
+            len:     0,
+        }
+    }
+
+    /// Adds a measurement and returns the updated estimate
+    pub fn push(&mut self, distance: f32) -> f32 {
+        self.samples[self.next] = distance;
+        self.next = (self.next + 1) % N;
+        if self.len < N {
+            self.len += 1;
+        }
+
+        // We just added a sample, so there's always an estimate.
+        self.estimate().unwrap()
+    }
+
+    /// Returns the current estimate, or `None`, if no measurement was added
+    pub fn estimate(&self) -> Option<f32> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let sum: f32 = self.samples[..self.len].iter().sum();
+        Some(sum / self.len as f32)
+    }
+
+    /// Removes all measurements from the filter
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.len  = 0;
+    }
+}
+
+#[cfg(feature = "float")]
+impl<const N: usize> Default for RangeFilter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
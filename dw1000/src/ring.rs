@@ -0,0 +1,294 @@
+//! A ring buffer for handing received frames from an interrupt handler
+//!
+//! In an interrupt-driven design, the interrupt handler reads received frames
+//! from the DW1000, while the main loop processes them. [`RxRing`] is a
+//! fixed-capacity, lock-free ring buffer for passing the frames between the
+//! two. It doesn't allocate, and only requires atomic loads and stores, so it
+//! also works on targets without compare-and-swap instructions.
+//!
+//! The ring buffer is split into a [`Producer`], which is used to push frames
+//! (typically in the interrupt handler), and a [`Consumer`], which is used to
+//! pop them (typically in the main loop).
+//!
+//! # Example
+//!
+//! ``` rust
+//! use dw1000::{
+//!     ring::{ReceivedFrame, RxRing},
+//!     time::Instant,
+//! };
+//!
+//! let mut ring: RxRing<4> = RxRing::new();
+//! let (mut producer, mut consumer) = ring.split();
+//!
+//! let rx_time = Instant::new(0x1234).unwrap();
+//! let frame   = ReceivedFrame::new(b"frame", rx_time, rx_time);
+//! producer.push(frame).unwrap();
+//!
+//! let frame = consumer.pop().unwrap();
+//! assert_eq!(frame.bytes(), b"frame");
+//! assert!(consumer.pop().is_none());
+//! ```
+
+
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    },
+};
+
+use byte::BytesExt as _;
+use ieee802154::mac::FooterMode;
+
+use crate::{
+    mac,
+    time::Instant,
+    Message,
+};
+
+
+/// The maximum length of a received frame, in bytes
+pub const MAX_FRAME_LEN: usize = 127;
+
+
+/// A single-producer, single-consumer ring buffer of received frames
+///
+/// Holds up to `N` frames. See [module documentation](index.html).
+pub struct RxRing<const N: usize> {
+    slots: UnsafeCell<[ReceivedFrame; N]>,
+
+    /// Number of frames popped so far, modulo `2 * N`
+    head: AtomicUsize,
+
+    /// Number of frames pushed so far, modulo `2 * N`
+    tail: AtomicUsize,
+}
+
+// The producer only writes to slots the consumer doesn't access, and vice
+// versa, as determined by `head` and `tail`.
+unsafe impl<const N: usize> Sync for RxRing<N> {}
+
+impl<const N: usize> RxRing<N> {
+    /// Fails to compile, if the ring buffer has no room for any frames
+    const NOT_EMPTY: () = assert!(N > 0, "`RxRing` needs room for a frame");
+
+    /// Creates an empty ring buffer
+    ///
+    /// `N` must not be zero. This is checked at compile time:
+    ///
+    /// ``` rust,compile_fail
+    /// use dw1000::ring::RxRing;
+    ///
+    /// let ring: RxRing<0> = RxRing::new();
+    /// ```
+    pub const fn new() -> Self {
+        let () = Self::NOT_EMPTY;
+
+        RxRing {
+            slots: UnsafeCell::new([ReceivedFrame::EMPTY; N]),
+            head:  AtomicUsize::new(0),
+            tail:  AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the ring buffer into producer and consumer
+    ///
+    /// The exclusive borrow makes sure that there's only ever one producer and
+    /// one consumer at a time.
+    pub fn split(&mut self) -> (Producer<'_, N>, Consumer<'_, N>) {
+        let ring: &Self = self;
+
+        let producer = Producer { ring, _not_sync: PhantomData };
+        let consumer = Consumer { ring, _not_sync: PhantomData };
+
+        (producer, consumer)
+    }
+
+    fn slot(&self, index: usize) -> *mut ReceivedFrame {
+        // `index % N` is always in bounds.
+        unsafe { (self.slots.get() as *mut ReceivedFrame).add(index % N) }
+    }
+
+    /// Returns the number of frames in the ring buffer
+    ///
+    /// The counters wrap around at `2 * N`, not at `usize::MAX + 1`. That way,
+    /// they stay consistent with the slot indices, even if `N` is not a power
+    /// of two, while full and empty can still be told apart.
+    fn count(head: usize, tail: usize) -> usize {
+        (tail + 2 * N - head) % (2 * N)
+    }
+
+    fn next(index: usize) -> usize {
+        (index + 1) % (2 * N)
+    }
+}
+
+impl<const N: usize> Default for RxRing<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// The pushing half of an [`RxRing`]
+pub struct Producer<'r, const N: usize> {
+    ring: &'r RxRing<N>,
+
+    // Only one context must push at a time.
+    _not_sync: PhantomData<*const ()>,
+}
+
+unsafe impl<'r, const N: usize> Send for Producer<'r, N> {}
+
+impl<'r, const N: usize> Producer<'r, N> {
+    /// Pushes a frame into the ring buffer
+    ///
+    /// Returns [`Full`], if the ring buffer is full. The frame is not pushed
+    /// in that case.
+    pub fn push(&mut self, frame: ReceivedFrame) -> Result<(), Full> {
+        let head = self.ring.head.load(Ordering::Acquire);
+        let tail = self.ring.tail.load(Ordering::Relaxed);
+
+        if RxRing::<N>::count(head, tail) >= N {
+            return Err(Full);
+        }
+
+        // The consumer doesn't access this slot, until we've incremented
+        // `tail`.
+        unsafe { self.ring.slot(tail).write(frame) };
+        self.ring.tail.store(RxRing::<N>::next(tail), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Indicates whether the ring buffer is full
+    pub fn is_full(&self) -> bool {
+        let head = self.ring.head.load(Ordering::Acquire);
+        let tail = self.ring.tail.load(Ordering::Relaxed);
+
+        RxRing::<N>::count(head, tail) >= N
+    }
+}
+
+
+/// The popping half of an [`RxRing`]
+pub struct Consumer<'r, const N: usize> {
+    ring: &'r RxRing<N>,
+
+    // Only one context must pop at a time.
+    _not_sync: PhantomData<*const ()>,
+}
+
+unsafe impl<'r, const N: usize> Send for Consumer<'r, N> {}
+
+impl<'r, const N: usize> Consumer<'r, N> {
+    /// Pops the oldest frame from the ring buffer
+    ///
+    /// Returns `None`, if the ring buffer is empty.
+    pub fn pop(&mut self) -> Option<ReceivedFrame> {
+        let head = self.ring.head.load(Ordering::Relaxed);
+        let tail = self.ring.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // The producer doesn't access this slot, until we've incremented
+        // `head`.
+        let frame = unsafe { self.ring.slot(head).read() };
+        self.ring.head.store(RxRing::<N>::next(head), Ordering::Release);
+
+        Some(frame)
+    }
+
+    /// Indicates whether the ring buffer is empty
+    pub fn is_empty(&self) -> bool {
+        let head = self.ring.head.load(Ordering::Relaxed);
+        let tail = self.ring.tail.load(Ordering::Acquire);
+
+        head == tail
+    }
+}
+
+
+/// Indicates that an [`RxRing`] is full
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Full;
+
+
+/// A received frame, as stored in an [`RxRing`]
+///
+/// Contains the raw bytes of the frame (without the FCS), and the RX time
+/// stamps.
+#[derive(Clone, Copy)]
+pub struct ReceivedFrame {
+    data: [u8; MAX_FRAME_LEN],
+    len:  usize,
+
+    /// The time the frame was received
+    ///
+    /// See [`Message::rx_time`].
+    pub rx_time: Instant,
+
+    /// The raw time the frame was received
+    ///
+    /// See [`Message::rx_time_raw`].
+    pub rx_time_raw: Instant,
+}
+
+impl ReceivedFrame {
+    const EMPTY: Self = ReceivedFrame {
+        data:        [0; MAX_FRAME_LEN],
+        len:         0,
+        rx_time:     Instant::ZERO,
+        rx_time_raw: Instant::ZERO,
+    };
+
+    /// Creates a received frame from raw bytes and RX time stamps
+    ///
+    /// `bytes` is truncated to [`MAX_FRAME_LEN`].
+    pub fn new(bytes: &[u8], rx_time: Instant, rx_time_raw: Instant) -> Self {
+        let len = bytes.len().min(MAX_FRAME_LEN);
+
+        let mut data = [0; MAX_FRAME_LEN];
+        data[..len].copy_from_slice(&bytes[..len]);
+
+        ReceivedFrame {
+            data,
+            len,
+            rx_time,
+            rx_time_raw,
+        }
+    }
+
+    /// Returns the raw bytes of the frame, without the FCS
+    pub fn bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Decodes the MAC frame
+    pub fn frame(&self) -> Result<mac::Frame<'_>, byte::Error> {
+        self.bytes().read_with(&mut 0, FooterMode::None)
+    }
+}
+
+// Can't be derived without printing all 127 bytes of the buffer.
+impl fmt::Debug for ReceivedFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReceivedFrame")
+            .field("bytes", &self.bytes())
+            .field("rx_time", &self.rx_time)
+            .field("rx_time_raw", &self.rx_time_raw)
+            .finish()
+    }
+}
+
+impl<'l> From<&Message<'l>> for ReceivedFrame {
+    fn from(message: &Message<'l>) -> Self {
+        ReceivedFrame::new(message.bytes, message.rx_time, message.rx_time_raw)
+    }
+}
@@ -1,7 +1,13 @@
 //! Time-related types based on the DW1000's system time
 
 
-use core::ops::Add;
+use core::{
+    convert::TryFrom as _,
+    ops::{
+        Add,
+        Sub,
+    },
+};
 use serde::{Serialize, Deserialize};
 
 
@@ -21,6 +27,8 @@ pub const TIME_MAX: u64 = 0xffffffffff;
 pub struct Instant(u64);
 
 impl Instant {
+    pub(crate) const ZERO: Instant = Instant(0);
+
     /// Creates a new instance of `Instant`
     ///
     /// The given value must fit in a 40-bit timestamp, so:
@@ -60,6 +68,32 @@ impl Instant {
         self.0
     }
 
+    /// Returns the timestamp in picoseconds, as a 48.16 fixed-point number
+    ///
+    /// One time unit is 1 / (128 * 499.2 MHz), or roughly 15.65 ps. The upper
+    /// 48 bits of the returned value are whole picoseconds, the lower 16 bits
+    /// are the fractional part. Rounding to whole nanoseconds would lose about
+    /// 6 bits of precision, which matters for precise ranging.
+    ///
+    /// The conversion rounds down, so the raw timestamp (see
+    /// [`Instant::value`]) remains the exact representation, and should be
+    /// used for any further calculations.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use dw1000::time::Instant;
+    ///
+    /// let instant = Instant::new(1).unwrap();
+    ///
+    /// let picos = instant.as_picos_fractional();
+    /// assert_eq!(picos >> 16, 15);
+    /// assert_eq!(picos & 0xffff, 42_601); // 0.65 ps
+    /// ```
+    pub fn as_picos_fractional(&self) -> u64 {
+        picos_fractional(self.0)
+    }
+
     /// Returns the amount of time passed between the two `Instant`s
     ///
     /// Assumes that `&self` represents a later time than the argument
@@ -115,6 +149,75 @@ impl Add<Duration> for Instant {
     }
 }
 
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        // Both `Instant` and `Duration` are guaranteed to contain 40-bit
+        // numbers, so wrapping around once is always enough.
+        let value = if self.value() >= rhs.value() {
+            self.value() - rhs.value()
+        }
+        else {
+            TIME_MAX - rhs.value() + self.value() + 1
+        };
+
+        // We made sure to keep the result within `TIME_MAX`, so the following
+        // will never panic.
+        Instant::new(value).unwrap()
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    /// Returns the duration since `rhs`
+    ///
+    /// Equivalent to [`Instant::duration_since`], including its handling of
+    /// wrap-around.
+    fn sub(self, rhs: Instant) -> Self::Output {
+        self.duration_since(rhs)
+    }
+}
+
+
+/// Computes the time of the next transmission in a periodic schedule
+///
+/// Returns `last + period`, wrapping around at the end of the 40-bit range.
+/// `last` should be the TX timestamp of the previous transmission (see
+/// [`DW1000::tx_time`]), which means the schedule is driven by the DW1000's
+/// clock, and doesn't accumulate any jitter from the host.
+///
+/// The DW1000 ignores the low 9 bits of the delayed transmission time. To get
+/// an exact period, `period` should be a multiple of 512 time units (roughly
+/// 8 ns). [`DW1000::send_periodic`] uses this function, and also takes the TX
+/// antenna delay into account.
+///
+/// # Example
+///
+/// ``` rust
+/// use dw1000::time::{
+///     TIME_MAX,
+///     Duration,
+///     Instant,
+///     next_periodic_tx,
+/// };
+///
+/// let period = Duration::new(512 * 1000).unwrap();
+///
+/// let last = Instant::new(1024).unwrap();
+/// assert_eq!(next_periodic_tx(last, period).value(), 1024 + 512 * 1000);
+///
+/// let last = Instant::new(TIME_MAX - 511).unwrap();
+/// assert_eq!(next_periodic_tx(last, period).value(), 512 * 999);
+/// ```
+///
+/// [`DW1000::tx_time`]: ../hl/struct.DW1000.html#method.tx_time
+/// [`DW1000::send_periodic`]: ../hl/struct.DW1000.html#method.send_periodic
+pub fn next_periodic_tx(last: Instant, period: Duration) -> Instant {
+    last + period
+}
+
 
 /// A duration between two instants in DW1000 system time
 ///
@@ -170,4 +273,121 @@ impl Duration {
     pub fn value(&self) -> u64 {
         self.0
     }
+
+    /// Returns the duration in picoseconds, as a 48.16 fixed-point number
+    ///
+    /// See [`Instant::as_picos_fractional`].
+    pub fn as_picos_fractional(&self) -> u64 {
+        picos_fractional(self.0)
+    }
+}
+
+
+/// Converts 40-bit time units into picoseconds, in 48.16 fixed-point format
+///
+/// One time unit is 10^7 / (2^14 * 39) ps. Shifted left by 16 bits, that
+/// leaves an exact factor of 4 * 10^7 / 39.
+fn picos_fractional(value: u64) -> u64 {
+    // `value` has at most 40 bits, so the product fits in `u128`, and the
+    // result fits in 60 bits.
+    (value as u128 * 40_000_000 / 39) as u64
+}
+
+
+/// Converts DW1000 system time into the time base of a host clock
+///
+/// DW1000 timestamps wrap around every 17.2 seconds and are based on the
+/// DW1000's crystal, which drifts relative to any clock on the host. This
+/// converter relates both clocks, so DW1000 timestamps can be put on the host's
+/// timeline (for example, for logging).
+///
+/// Pass pairs of a DW1000 timestamp and a host timestamp, taken at the same
+/// time, to [`TimeConverter::sync`]. The host timestamp can be in any unit, as
+/// long as it's monotonic. The converter only keeps the last sync point and the
+/// rate between both clocks, as measured between the last two sync points.
+///
+/// Since DW1000 timestamps wrap around, the converter can't tell how many times
+/// that happened between two timestamps. Sync points must be less than 17.2
+/// seconds apart, and converted timestamps must be within 8.6 seconds of the
+/// last sync point.
+///
+/// # Example
+///
+/// ``` rust
+/// use dw1000::time::{
+///     Instant,
+///     TimeConverter,
+/// };
+///
+/// // The host clock counts microseconds. One microsecond is 128 * 499.2, or
+/// // 63897.6, DW1000 time units.
+/// let mut converter = TimeConverter::new();
+/// converter.sync(Instant::new(0).unwrap(), 1_000);
+/// converter.sync(Instant::new(63_897_600).unwrap(), 2_000);
+///
+/// // Extrapolate after the last sync point
+/// let instant = Instant::new(95_846_400).unwrap();
+/// assert_eq!(converter.to_host(instant), Some(2_500));
+///
+/// // Interpolate before the last sync point
+/// let instant = Instant::new(31_948_800).unwrap();
+/// assert_eq!(converter.to_host(instant), Some(1_500));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TimeConverter {
+    last: Option<(Instant, u64)>,
+
+    /// Host time elapsed and DW1000 time elapsed between the last two syncs
+    rate: Option<(u64, u64)>,
+}
+
+impl TimeConverter {
+    /// Creates a new converter without any sync points
+    pub fn new() -> Self {
+        TimeConverter {
+            last: None,
+            rate: None,
+        }
+    }
+
+    /// Adds a sync point
+    ///
+    /// `instant` and `host` must have been taken at the same time. At least two
+    /// sync points are required, before timestamps can be converted.
+    pub fn sync(&mut self, instant: Instant, host: u64) {
+        if let Some((last_instant, last_host)) = self.last {
+            let dw1000_elapsed = instant.duration_since(last_instant).value();
+            let host_elapsed   = host.saturating_sub(last_host);
+
+            if dw1000_elapsed != 0 {
+                self.rate = Some((host_elapsed, dw1000_elapsed));
+            }
+        }
+
+        self.last = Some((instant, host));
+    }
+
+    /// Converts a DW1000 timestamp into host time
+    ///
+    /// Returns `None`, if less than two sync points have been added, or if the
+    /// result would be outside of the range of `u64`.
+    pub fn to_host(&self, instant: Instant) -> Option<u64> {
+        let (last_instant, last_host)      = self.last?;
+        let (host_elapsed, dw1000_elapsed) = self.rate?;
+
+        // Timestamps more than half the range away are assumed to be before
+        // the last sync point.
+        let elapsed = instant.duration_since(last_instant).value() as i128;
+        let elapsed = if elapsed > (TIME_MAX / 2) as i128 {
+            elapsed - (TIME_MAX + 1) as i128
+        }
+        else {
+            elapsed
+        };
+
+        let host = last_host as i128
+            + elapsed * host_elapsed as i128 / dw1000_elapsed as i128;
+
+        u64::try_from(host).ok()
+    }
 }
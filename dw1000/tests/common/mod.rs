@@ -0,0 +1,198 @@
+//! Mock SPI implementation for testing the driver on the host
+//!
+//! The mock decodes the DW1000's SPI transaction headers, records all register
+//! accesses in order, keeps the data of all writes, and answers register reads
+//! with values that were set up in advance. Registers that haven't been set up
+//! read as all zeros. Writes don't affect what is read back, which keeps the
+//! driver's busy-waiting loops from hanging. To model a register that changes
+//! over time, a sequence of values can be queued, which are returned by
+//! consecutive reads.
+
+
+#![allow(dead_code)]
+
+
+use std::{
+    cell::RefCell,
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    convert::Infallible,
+    rc::Rc,
+};
+
+use embedded_hal::{
+    blocking::spi,
+    digital::v2::OutputPin,
+};
+
+
+/// A register write, as recorded by the mock
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Write {
+    pub id:     u8,
+    pub sub_id: u16,
+    pub data:   Vec<u8>,
+}
+
+
+/// A register access, as recorded by the mock
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Access {
+    Read(u8, u16),
+    Write(u8, u16),
+}
+
+
+#[derive(Default)]
+struct State {
+    reads:    HashMap<(u8, u16), Vec<u8>>,
+    queued:   HashMap<(u8, u16), VecDeque<Vec<u8>>>,
+    writes:   Vec<Write>,
+    accesses: Vec<Access>,
+}
+
+
+/// Mock SPI bus
+///
+/// Clones share the same state, so one clone can be handed to the driver,
+/// while the other is used to inspect what the driver did.
+#[derive(Clone, Default)]
+pub struct MockSpi(Rc<RefCell<State>>);
+
+impl MockSpi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the data returned when reading the register
+    pub fn set_read(&self, id: u8, sub_id: u16, data: &[u8]) {
+        self.0.borrow_mut().reads.insert((id, sub_id), data.to_vec());
+    }
+
+    /// Queues data to be returned by the next read of the register
+    ///
+    /// Queued data is returned once, in order. Once the queue is empty, reads
+    /// return the data set by `set_read` again.
+    pub fn queue_read(&self, id: u8, sub_id: u16, data: &[u8]) {
+        self.0.borrow_mut()
+            .queued
+            .entry((id, sub_id))
+            .or_default()
+            .push_back(data.to_vec());
+    }
+
+    /// Returns all recorded writes
+    pub fn writes(&self) -> Vec<Write> {
+        self.0.borrow().writes.clone()
+    }
+
+    /// Returns all recorded writes to the register
+    pub fn writes_to(&self, id: u8, sub_id: u16) -> Vec<Vec<u8>> {
+        self.writes()
+            .into_iter()
+            .filter(|write| write.id == id && write.sub_id == sub_id)
+            .map(|write| write.data)
+            .collect()
+    }
+
+    /// Returns the data of the last write to the register
+    pub fn last_write_to(&self, id: u8, sub_id: u16) -> Option<Vec<u8>> {
+        self.writes_to(id, sub_id).pop()
+    }
+
+    /// Returns all recorded reads and writes, in the order they happened
+    pub fn accesses(&self) -> Vec<Access> {
+        self.0.borrow().accesses.clone()
+    }
+}
+
+impl spi::Transfer<u8> for MockSpi {
+    type Error = Infallible;
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8])
+        -> Result<&'w [u8], Self::Error>
+    {
+        let (is_write, id, sub_id, header_len) = parse_header(words);
+        assert!(!is_write, "Transfer used for writing");
+
+        let mut state = self.0.borrow_mut();
+        state.accesses.push(Access::Read(id, sub_id));
+
+        let data      = &mut words[header_len..];
+        for byte in data.iter_mut() {
+            *byte = 0;
+        }
+        let read = state.queued
+            .get_mut(&(id, sub_id))
+            .and_then(|queue| queue.pop_front())
+            .or_else(|| state.reads.get(&(id, sub_id)).cloned());
+        if let Some(read) = read {
+            let len = read.len().min(data.len());
+            data[..len].copy_from_slice(&read[..len]);
+        }
+
+        Ok(words)
+    }
+}
+
+impl spi::Write<u8> for MockSpi {
+    type Error = Infallible;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        let (is_write, id, sub_id, header_len) = parse_header(words);
+        assert!(is_write, "Write used for reading");
+
+        let mut state = self.0.borrow_mut();
+        state.accesses.push(Access::Write(id, sub_id));
+        state.writes.push(Write {
+            id,
+            sub_id,
+            data: words[header_len..].to_vec(),
+        });
+
+        Ok(())
+    }
+}
+
+
+/// Mock chip select pin
+pub struct MockPin;
+
+impl OutputPin for MockPin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+
+/// Decodes a transaction header. See user manual, section 2.2.1.2.
+///
+/// Returns whether this is a write, the register ID, the sub-index, and the
+/// length of the header.
+fn parse_header(words: &[u8]) -> (bool, u8, u16, usize) {
+    let is_write  = words[0] & 0x80 != 0;
+    let has_sub   = words[0] & 0x40 != 0;
+    let id        = words[0] & 0x3f;
+
+    if !has_sub {
+        return (is_write, id, 0, 1);
+    }
+
+    let extended = words[1] & 0x80 != 0;
+    let low      = (words[1] & 0x7f) as u16;
+
+    if !extended {
+        return (is_write, id, low, 2);
+    }
+
+    let sub_id = low | (words[2] as u16) << 7;
+    (is_write, id, sub_id, 3)
+}
@@ -0,0 +1,18 @@
+//! Tests for the configuration types
+
+
+use dw1000::configs::PulseRepetitionFrequency;
+
+
+#[test]
+fn preamble_symbol_duration_should_match_prf() {
+    // 993.59 ns and 1017.63 ns, in units of 1/64 ns. See user manual, table 6.
+    assert_eq!(
+        PulseRepetitionFrequency::Mhz16.get_preamble_symbol_duration(),
+        63_590,
+    );
+    assert_eq!(
+        PulseRepetitionFrequency::Mhz64.get_preamble_symbol_duration(),
+        65_128,
+    );
+}
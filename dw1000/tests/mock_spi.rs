@@ -0,0 +1,526 @@
+mod common;
+
+
+use dw1000::{
+    configs::{
+        BitRate,
+        UwbChannel,
+    },
+    frame,
+    hl,
+    ll,
+    mac,
+    queue::{
+        Pump,
+        QueuedFrame,
+        TxQueue,
+    },
+    Error,
+    RxConfig,
+    TxConfig,
+    DW1000,
+};
+
+use embedded_hal::timer::CountDown;
+use void::Void;
+
+use self::common::{
+    Access,
+    MockPin,
+    MockSpi,
+};
+
+
+/// DEV_ID value of the DW1000, in little-endian byte order
+const DEV_ID: [u8; 4] = [0x30, 0x01, 0xca, 0xde];
+
+
+/// Initializes the driver, with the mock set up to answer like a DW1000
+fn init_dw1000(spi: &MockSpi) -> DW1000<MockSpi, MockPin, hl::Ready> {
+    spi.set_read(0x00, 0x00, &DEV_ID);
+    DW1000::new(spi.clone(), MockPin).init().unwrap()
+}
+
+
+#[test]
+fn ll_should_pack_fields_across_byte_boundaries() {
+    let spi = MockSpi::new();
+    let mut dw1000 = ll::DW1000::new(spi.clone(), MockPin);
+
+    dw1000.tx_fctrl()
+        .write(|w|
+            w
+                .tflen(0x12)
+                .txbr(0b10)
+                .tr(0b1)
+                .txprf(0b01)
+                .txpsr(0b01)
+                .pe(0b01)
+                .txboffs(0x155)
+                .ifsdelay(0xab)
+        )
+        .unwrap();
+
+    assert_eq!(
+        spi.last_write_to(0x08, 0x00),
+        Some(vec![0x12, 0xc0, 0x55, 0x55, 0xab]),
+    );
+}
+
+#[test]
+fn ll_should_unpack_fields_across_byte_boundaries() {
+    let spi = MockSpi::new();
+    spi.set_read(0x10, 0x00, &[0x25, 0xc0, 0x11, 0x3c]);
+    let mut dw1000 = ll::DW1000::new(spi, MockPin);
+
+    let rx_finfo = dw1000.rx_finfo().read().unwrap();
+
+    assert_eq!(rx_finfo.rxflen(), 0x25);
+    assert_eq!(rx_finfo.rxfle(), 0b000);
+    assert_eq!(rx_finfo.rxbr(), 0b10);
+    assert_eq!(rx_finfo.rng(), 0b1);
+    assert_eq!(rx_finfo.rxprfr(), 0b01);
+    assert_eq!(rx_finfo.rxpsr(), 0b00);
+    assert_eq!(rx_finfo.rxpacc(), 0x3c1);
+}
+
+#[test]
+fn ll_should_use_extended_sub_index() {
+    let spi = MockSpi::new();
+    let mut dw1000 = ll::DW1000::new(spi.clone(), MockPin);
+
+    dw1000.lde_cfg2().write(|w| w.value(0x1607)).unwrap();
+
+    assert_eq!(spi.last_write_to(0x2e, 0x1806), Some(vec![0x07, 0x16]));
+}
+
+#[test]
+fn ll_should_split_long_transfers() {
+    let spi = MockSpi::new();
+    let mut dw1000 = ll::DW1000::new(spi.clone(), MockPin);
+    dw1000.set_max_transfer_len(6);
+
+    dw1000.write_tx_buffer_at(0, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).unwrap();
+
+    assert_eq!(
+        spi.writes(),
+        vec![
+            common::Write { id: 0x09, sub_id: 0, data: vec![1, 2, 3] },
+            common::Write { id: 0x09, sub_id: 3, data: vec![4, 5, 6] },
+            common::Write { id: 0x09, sub_id: 6, data: vec![7, 8, 9] },
+            common::Write { id: 0x09, sub_id: 9, data: vec![10] },
+        ],
+    );
+}
+
+#[test]
+fn init_should_detect_missing_device() {
+    let spi = MockSpi::new();
+
+    let result = DW1000::new(spi.clone(), MockPin).init();
+
+    assert!(matches!(result, Err(Error::NoResponse)));
+    assert!(spi.writes().is_empty());
+}
+
+#[test]
+fn init_should_write_recommended_values() {
+    let spi = MockSpi::new();
+    init_dw1000(&spi);
+
+    // See user manual, section 2.5.5.
+    assert_eq!(spi.last_write_to(0x23, 0x04), Some(vec![0x70, 0x88]));
+    assert_eq!(
+        spi.last_write_to(0x23, 0x0c),
+        Some(vec![0x07, 0xa9, 0x02, 0x25]),
+    );
+    assert_eq!(
+        spi.last_write_to(0x27, 0x08),
+        Some(vec![0x2d, 0x00, 0x1a, 0x31]),
+    );
+    assert_eq!(spi.last_write_to(0x2e, 0x1806), Some(vec![0x07, 0x16]));
+    assert_eq!(
+        spi.last_write_to(0x1e, 0x00),
+        Some(vec![0x48, 0x28, 0x08, 0x0e]),
+    );
+    assert_eq!(spi.last_write_to(0x2a, 0x0b), Some(vec![0xc0]));
+    assert_eq!(spi.last_write_to(0x2b, 0x0b), Some(vec![0xbe]));
+}
+
+#[test]
+fn send_should_encode_frame() {
+    let spi = MockSpi::new();
+    spi.set_read(0x03, 0x00, &[0x01, 0x00, 0x57, 0x0d]);
+
+    let dw1000 = init_dw1000(&spi);
+
+    let destination = mac::Address::Short(
+        mac::PanId(0x0d57),
+        mac::ShortAddress(0x0002),
+    );
+    dw1000
+        .send(b"hello", Some(destination), None, TxConfig::default())
+        .unwrap();
+
+    let tx_buffer = spi.last_write_to(0x09, 0x00).unwrap();
+    assert_eq!(&tx_buffer[2..3],   &[0x00]); // sequence number
+    assert_eq!(&tx_buffer[3..7],   &[0x57, 0x0d, 0x02, 0x00]); // destination
+    assert_eq!(&tx_buffer[7..11],  &[0x57, 0x0d, 0x01, 0x00]); // source
+    assert_eq!(&tx_buffer[11..16], b"hello");
+
+    // The frame length includes the 2-byte CRC.
+    let tx_fctrl = spi.last_write_to(0x08, 0x00).unwrap();
+    assert_eq!(tx_fctrl[0] & 0x7f, 18);
+}
+
+#[test]
+fn send_should_convert_tx_power_for_long_frames() {
+    let spi = MockSpi::new();
+    spi.set_read(0x1e, 0x00, &[0x48, 0x28, 0x08, 0x0e]);
+
+    let dw1000 = init_dw1000(&spi);
+
+    // Far longer than 1 ms at 110 kbps: Smart TX power is disabled, and the
+    // normal power setting is used for the whole frame.
+    let config = TxConfig {
+        bitrate: BitRate::Kbps110,
+        .. TxConfig::default()
+    };
+    let dw1000 = dw1000
+        .send(&[0; 100], None, None, config)
+        .unwrap()
+        .finish_sending()
+        .unwrap();
+
+    assert_eq!(spi.last_write_to(0x04, 0x00).unwrap()[2] & 0x04, 0x04);
+    assert_eq!(spi.last_write_to(0x1e, 0x00), Some(vec![0x48; 4]));
+
+    // Short frame: The smart settings are restored.
+    spi.set_read(0x04, 0x00, &[0x00, 0x00, 0x04, 0x00]);
+    dw1000.send(b"hello", None, None, TxConfig::default()).unwrap();
+
+    assert_eq!(spi.last_write_to(0x04, 0x00).unwrap()[2] & 0x04, 0x00);
+    assert_eq!(
+        spi.last_write_to(0x1e, 0x00),
+        Some(vec![0x48, 0x28, 0x08, 0x0e]),
+    );
+}
+
+#[test]
+fn send_should_succeed_after_pll_recovered() {
+    let spi = MockSpi::new();
+
+    // RFPLL_LL set: Sending fails, and the losing lock flags are cleared.
+    spi.set_read(0x0f, 0x00, &[0x00, 0x00, 0x00, 0x01, 0x00]);
+    let result = init_dw1000(&spi)
+        .send(b"hello", None, None, TxConfig::default());
+    assert!(matches!(result, Err(Error::PllNotLocked)));
+    assert_eq!(spi.last_write_to(0x0f, 0x00).unwrap()[3] & 0x03, 0x03);
+
+    // The PLL has locked again.
+    spi.set_read(0x0f, 0x00, &[0x02, 0x00, 0x00, 0x00, 0x00]);
+    init_dw1000(&spi)
+        .send(b"hello", None, None, TxConfig::default())
+        .unwrap();
+}
+
+#[test]
+fn send_should_set_fcs_suppression_per_frame() {
+    let spi = MockSpi::new();
+    spi.set_read(0x03, 0x00, &[0x01, 0x00, 0x57, 0x0d]);
+
+    let dw1000 = init_dw1000(&spi);
+
+    let destination = mac::Address::Short(
+        mac::PanId(0x0d57),
+        mac::ShortAddress(0x0002),
+    );
+
+    // FCS suppressed: The frame is sent as-is, without an appended FCS.
+    let config = TxConfig { suppress_fcs: true, .. TxConfig::default() };
+    let dw1000 = dw1000
+        .send(b"hello", Some(destination), None, config)
+        .unwrap()
+        .finish_sending()
+        .unwrap();
+
+    // `finish_sending` forces the DW1000 into IDLE mode afterwards, so look
+    // for the SYS_CTRL write that started the transmission.
+    let tx_fctrl = spi.last_write_to(0x08, 0x00).unwrap();
+    let sys_ctrl = tx_start(&spi);
+    assert_eq!(tx_fctrl[0] & 0x7f, 16);
+    assert_eq!(sys_ctrl[0] & 0x0b, 0x03); // SFCST and TXSTRT
+
+    // Regular frame: The suppression must have been cancelled.
+    dw1000
+        .send(b"hello", Some(destination), None, TxConfig::default())
+        .unwrap();
+
+    let tx_fctrl = spi.last_write_to(0x08, 0x00).unwrap();
+    let sys_ctrl = tx_start(&spi);
+    assert_eq!(tx_fctrl[0] & 0x7f, 18);
+    assert_eq!(sys_ctrl[0] & 0x0b, 0x0a); // CANSFCS and TXSTRT
+}
+
+/// Returns the last write to SYS_CTRL that set TXSTRT
+fn tx_start(spi: &MockSpi) -> Vec<u8> {
+    spi.writes_to(0x0d, 0x00)
+        .into_iter()
+        .rev()
+        .find(|sys_ctrl| sys_ctrl[0] & 0x02 != 0)
+        .unwrap()
+}
+
+#[test]
+fn send_raw_should_send_from_buffer_offset() {
+    let spi = MockSpi::new();
+    let mut dw1000 = init_dw1000(&spi);
+
+    dw1000.ll().write_tx_buffer_at(200, b"hello").unwrap();
+    dw1000.send_raw(200, 5, None, TxConfig::default()).unwrap();
+
+    assert_eq!(spi.last_write_to(0x09, 200), Some(b"hello".to_vec()));
+
+    // TFLEN includes the 2-byte CRC. TXBOFFS starts at bit 22.
+    let tx_fctrl = spi.last_write_to(0x08, 0x00).unwrap();
+    assert_eq!(tx_fctrl[0] & 0x7f, 7);
+    let txboffs = (tx_fctrl[2] >> 6) as u16 | (tx_fctrl[3] as u16) << 2;
+    assert_eq!(txboffs, 200);
+}
+
+#[test]
+fn send_raw_should_encode_extended_frame_length() {
+    let spi = MockSpi::new();
+    let dw1000 = init_dw1000(&spi);
+
+    // Too long for a standard frame
+    let result = dw1000.send_raw(0, 200, None, TxConfig::default());
+    assert!(matches!(result, Err(Error::FrameTooLong)));
+
+    // PHR_MODE in SYS_CFG, as if set by `set_phr_mode`
+    spi.set_read(0x04, 0x00, &[0x00, 0x00, 0x03, 0x00]);
+    let dw1000 = init_dw1000(&spi);
+    dw1000.send_raw(0, 200, None, TxConfig::default()).unwrap();
+
+    // 202 bytes, including the FCS, split into TFLEN and TFLE.
+    let tx_fctrl = spi.last_write_to(0x08, 0x00).unwrap();
+    assert_eq!(tx_fctrl[0] & 0x7f, 202 & 0x7f);
+    assert_eq!(tx_fctrl[0] >> 7 | (tx_fctrl[1] & 0x03) << 1, 202 >> 7);
+}
+
+#[test]
+fn tx_buffer_access_should_not_overflow() {
+    let spi = MockSpi::new();
+    let mut dw1000 = init_dw1000(&spi);
+
+    let result = dw1000.ll().write_tx_buffer_at(1020, &[0; 5]);
+    assert!(matches!(result, Err(ll::Error::OutOfBounds)));
+    assert!(spi.writes_to(0x09, 1020).is_empty());
+
+    let result = dw1000.set_tx_buffer_offset(1024);
+    assert!(matches!(result, Err(Error::TxBufferOverflow)));
+
+    let result = dw1000.send_raw(1020, 3, None, TxConfig::default());
+    assert!(matches!(result, Err(Error::TxBufferOverflow)));
+}
+
+#[test]
+fn tx_time_should_be_available_after_wait() {
+    let spi = MockSpi::new();
+    spi.set_read(0x17, 0x00, &[0x05, 0x04, 0x03, 0x02, 0x01]);
+
+    let dw1000 = init_dw1000(&spi);
+    let mut sending = dw1000
+        .send(b"hello", None, None, TxConfig::default())
+        .unwrap();
+
+    // Frame sent
+    spi.set_read(0x0f, 0x00, &[0x80, 0x00, 0x00, 0x00, 0x00]);
+    let before_wait = spi.accesses().len();
+    sending.wait().unwrap();
+
+    // Waiting must have cleared the status bits, but only after reading the
+    // time stamp.
+    assert_eq!(spi.last_write_to(0x0f, 0x00).unwrap()[0] & 0x80, 0x80);
+    let accesses     = &spi.accesses()[before_wait..];
+    let position     = |access| accesses.iter().position(|a| *a == access);
+    let read_time    = position(Access::Read(0x17, 0x00)).unwrap();
+    let clear_status = position(Access::Write(0x0f, 0x00)).unwrap();
+    assert!(read_time < clear_status);
+
+    let tx_time = sending.tx_time().unwrap();
+    assert_eq!(tx_time.value(), 0x01_02_03_04_05);
+}
+
+#[test]
+fn queue_should_keep_frame_until_sent() {
+    let spi = MockSpi::new();
+    let dw1000 = init_dw1000(&spi);
+
+    let mut storage = [QueuedFrame::empty(); 2];
+    let mut queue   = TxQueue::new(&mut storage);
+    queue.enqueue(b"first", None).unwrap();
+    queue.enqueue(b"second", None).unwrap();
+
+    // Aborted before the frame was sent: It stays in the queue.
+    let sending = match queue.pump(dw1000, TxConfig::default()).unwrap() {
+        Pump::Sending(sending, seq) => { assert_eq!(seq, 0); sending }
+        Pump::Empty(_)              => panic!("Queue should not be empty"),
+    };
+    let dw1000 = queue.finish(sending).unwrap();
+    assert_eq!(queue.len(), 2);
+
+    // Frame sent
+    let mut sending = match queue.pump(dw1000, TxConfig::default()).unwrap() {
+        Pump::Sending(sending, _) => sending,
+        Pump::Empty(_)            => panic!("Queue should not be empty"),
+    };
+    spi.set_read(0x0f, 0x00, &[0x80, 0x00, 0x00, 0x00, 0x00]);
+    sending.wait().unwrap();
+    queue.finish(sending).unwrap();
+    assert_eq!(queue.len(), 1);
+}
+
+#[test]
+fn receive_should_decode_secured_frame() {
+    let frame = [
+        0x49, 0x98,             // secured data frame, PAN ID compression
+        0x05,                   // sequence number
+        0x57, 0x0d, 0x02, 0x00, // destination PAN ID and address
+        0x01, 0x00,             // source address
+        0x0d,                   // security level 5, key identifier mode 1
+        0x04, 0x03, 0x02, 0x01, // frame counter
+        0x07,                   // key index
+        b's', b'e', b'c', b'r', b'e', b't',
+        0x00, 0x00,             // FCS
+    ];
+
+    let spi = MockSpi::new();
+    spi.set_read(0x0f, 0x00, &[0x00, 0x24, 0x00, 0x00, 0x00]); // RXDFR, LDEDONE
+    spi.set_read(0x10, 0x00, &[frame.len() as u8, 0x00, 0x00, 0x00]);
+    spi.set_read(0x11, 0x00, &frame);
+
+    let dw1000 = init_dw1000(&spi);
+    let mut receiving = dw1000.receive(RxConfig::default()).unwrap();
+
+    let mut buffer = [0; 127];
+    let message    = receiving.wait(&mut buffer).unwrap();
+
+    let security = message.security.unwrap();
+    assert_eq!(security.security_level, 5);
+    assert_eq!(security.frame_counter, 0x01_02_03_04);
+    assert_eq!(security.key_identifier, frame::KeyIdentifier::Index(7));
+
+    assert_eq!(message.frame.header.seq, 5);
+    assert_eq!(
+        message.frame.header.source,
+        Some(mac::Address::Short(mac::PanId(0x0d57), mac::ShortAddress(1))),
+    );
+    // The frame is decoded without a footer, so the FCS stays in the payload.
+    assert_eq!(message.frame.payload, b"secret\0\0");
+    assert_eq!(message.bytes, &frame[..frame.len() - 2]);
+
+    // The borrowed variant must decode it the same way.
+    let message = receiving.wait_borrowed().unwrap();
+    assert_eq!(message.security, Some(security));
+    assert_eq!(message.frame.payload, b"secret\0\0");
+}
+
+#[test]
+fn receive_should_configure_110_kbps() {
+    let spi = MockSpi::new();
+    let dw1000 = init_dw1000(&spi);
+    dw1000.receive(RxConfig::long_range()).unwrap();
+
+    // RXM110K in SYS_CFG. See user manual, section 7.2.6.
+    assert_eq!(
+        spi.last_write_to(0x04, 0x00),
+        Some(vec![0x00, 0x00, 0x40, 0x00]),
+    );
+
+    // DRX_TUNE0B, DRX_TUNE1B, and DRX_TUNE4H. See user manual, tables 30, 32,
+    // and 34.
+    assert_eq!(spi.last_write_to(0x27, 0x02), Some(vec![0x16, 0x00]));
+    assert_eq!(spi.last_write_to(0x27, 0x06), Some(vec![0x64, 0x00]));
+    assert_eq!(spi.last_write_to(0x27, 0x26), Some(vec![0x28, 0x00]));
+
+    // DRX_SFDTOC: preamble length + 1 + SFD length - PAC size, with the
+    // 64-symbol SFD used at 110 kbps.
+    assert_eq!(
+        spi.last_write_to(0x27, 0x20),
+        Some((2048u16 + 1 + 64 - 64).to_le_bytes().to_vec()),
+    );
+}
+
+#[test]
+fn clear_all_status_should_clear_every_writable_bit() {
+    let spi = MockSpi::new();
+    let mut dw1000 = init_dw1000(&spi);
+    dw1000.clear_all_status().unwrap();
+
+    // Bit 0 (IRQS), bit 19 (reserved), and bits 30 and 31 (HSRBP, ICRBP) are
+    // not writable. See user manual, section 7.2.17.
+    assert_eq!(
+        spi.last_write_to(0x0f, 0x00),
+        Some(vec![0xfe, 0xff, 0xf7, 0x3f, 0x07]),
+    );
+}
+
+#[test]
+fn set_channel_should_wait_for_pll_lock() {
+    let spi = MockSpi::new();
+    let mut dw1000 = init_dw1000(&spi);
+
+    // CPLOCK is only set on the third poll.
+    spi.queue_read(0x0f, 0x00, &[0x00; 5]);
+    spi.queue_read(0x0f, 0x00, &[0x00; 5]);
+    spi.set_read(0x0f, 0x00, &[0x02, 0x00, 0x00, 0x00, 0x00]);
+
+    let mut timer = MockTimer::new(10);
+    dw1000.set_channel(UwbChannel::Channel2, &mut timer).unwrap();
+    assert_eq!(timer.waits, 2);
+
+    // CPLOCK, RFPLL_LL, and CLKPLL_LL must have been cleared after retuning.
+    assert_eq!(
+        spi.last_write_to(0x0f, 0x00),
+        Some(vec![0x02, 0x00, 0x00, 0x03, 0x00]),
+    );
+}
+
+#[test]
+fn set_channel_should_time_out_without_pll_lock() {
+    let spi = MockSpi::new();
+    let mut dw1000 = init_dw1000(&spi);
+
+    let mut timer = MockTimer::new(3);
+    let result = dw1000.set_channel(UwbChannel::Channel2, &mut timer);
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(timer.waits, 3);
+}
+
+
+/// Timer that expires after it has been polled a number of times
+struct MockTimer {
+    waits:         u32,
+    expires_after: u32,
+}
+
+impl MockTimer {
+    fn new(expires_after: u32) -> Self {
+        Self { waits: 0, expires_after }
+    }
+}
+
+impl CountDown for MockTimer {
+    type Time = ();
+
+    fn start<T>(&mut self, _: T) where T: Into<()> {}
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        self.waits += 1;
+        if self.waits < self.expires_after {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(())
+    }
+}
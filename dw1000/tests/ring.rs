@@ -0,0 +1,51 @@
+//! Tests for the receive ring buffer
+
+
+use dw1000::{
+    ring::{
+        Full,
+        ReceivedFrame,
+        RxRing,
+    },
+    time::Instant,
+};
+
+
+#[test]
+fn ring_should_keep_order_across_wrap_around() {
+    // Not a power of two, so the counters wrapping around must not affect the
+    // slot order.
+    let mut ring: RxRing<3> = RxRing::new();
+    let (mut producer, mut consumer) = ring.split();
+
+    let rx_time = Instant::new(0).unwrap();
+
+    for i in 0..20u8 {
+        producer.push(ReceivedFrame::new(&[i], rx_time, rx_time)).unwrap();
+        producer.push(ReceivedFrame::new(&[i, i], rx_time, rx_time)).unwrap();
+
+        assert_eq!(consumer.pop().unwrap().bytes(), &[i]);
+        assert_eq!(consumer.pop().unwrap().bytes(), &[i, i]);
+        assert!(consumer.is_empty());
+    }
+}
+
+#[test]
+fn ring_should_reject_frames_when_full() {
+    let mut ring: RxRing<3> = RxRing::new();
+    let (mut producer, mut consumer) = ring.split();
+
+    let rx_time = Instant::new(0).unwrap();
+
+    for i in 0..3u8 {
+        producer.push(ReceivedFrame::new(&[i], rx_time, rx_time)).unwrap();
+    }
+    assert!(producer.is_full());
+    assert_eq!(
+        producer.push(ReceivedFrame::new(&[3], rx_time, rx_time)),
+        Err(Full),
+    );
+
+    assert_eq!(consumer.pop().unwrap().bytes(), &[0]);
+    assert!(!producer.is_full());
+}